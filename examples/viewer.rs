@@ -0,0 +1,45 @@
+use std::io::{stdout, Result};
+
+use ratatui::{
+    backend::CrosstermBackend,
+    crossterm::{
+        event,
+        terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+        ExecutableCommand,
+    },
+    Terminal,
+};
+
+use ratatui_image::{Image, ViewState, Viewer};
+
+fn main() -> Result<()> {
+    let args: Vec<_> = std::env::args().collect();
+    if args.len() < 2 {
+        println!("Usage: {} <file>", args[0]);
+        return Ok(());
+    }
+    let image = Image::open(&args[1])?;
+    let viewer = Viewer::new(image.view());
+    let mut state = ViewState::new();
+
+    stdout().execute(EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    terminal.clear()?;
+    terminal.hide_cursor()?;
+
+    while !state.should_quit() {
+        terminal.draw(|frame| {
+            let area = frame.size();
+            frame.render_stateful_widget(viewer, area, &mut state);
+        })?;
+
+        if event::poll(std::time::Duration::from_millis(16))? {
+            state.handle_event(&event::read()?);
+        }
+    }
+
+    stdout().execute(LeaveAlternateScreen)?;
+    disable_raw_mode()?;
+    Ok(())
+}
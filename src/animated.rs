@@ -0,0 +1,383 @@
+use crate::{Image, ImageView, Result};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{StatefulWidget, Widget},
+};
+use std::io::{BufRead, Seek, SeekFrom};
+use std::time::Duration;
+
+/// How many times an [`AnimatedImage`] plays
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LoopCount {
+    /// The animation repeats forever
+    #[default]
+    Infinite,
+    /// The animation plays the given number of times, then stays on its last
+    /// frame. A count of `0` is treated as `1`.
+    Finite(u32),
+}
+
+/// A sequence of [`Image`] frames, each shown for a duration, such as decoded
+/// from an animated GIF, APNG, or WebP file.
+///
+/// Every frame is a full image of the same size, with any partial frame
+/// updates of the source already composited.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct AnimatedImage {
+    frames: Vec<(Image, Duration)>,
+    loop_count: LoopCount,
+}
+
+/// A widget rendering the current frame of an [`AnimatedImage`], according to
+/// the time elapsed in an [`AnimatedImageState`].
+///
+/// Frames are rendered exactly like an [`ImageView`] of the frame. Render
+/// options are taken from a view given to
+/// [`with_view`](AnimatedImageView::with_view), and default to those of
+/// [`Image::view`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimatedImageView<'a> {
+    animation: &'a AnimatedImage,
+    view: Option<ImageView<'a>>,
+}
+
+/// State of an [`AnimatedImageView`], holding the time elapsed since the start
+/// of the animation.
+///
+/// The time is advanced by the caller with [`tick`](AnimatedImageState::tick),
+/// typically in the event loop, and the frame shown at that time is selected
+/// when rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AnimatedImageState {
+    elapsed: Duration,
+    current: usize,
+}
+
+impl AnimatedImage {
+    /// Creates an animation from frames and the duration each is shown,
+    /// repeating forever
+    pub fn new(frames: Vec<(Image, Duration)>) -> Self {
+        Self {
+            frames,
+            loop_count: LoopCount::Infinite,
+        }
+    }
+
+    /// Loads an animation from a type implementing [`BufRead`] and [`Seek`].
+    /// The image format is automatically detected from the content.
+    ///
+    /// All frames of animated GIF, APNG, and WebP images are decoded (if the
+    /// corresponding features are enabled). Any other image, including a
+    /// PNG or WebP without animation, is loaded like [`Image::load`] as a
+    /// single frame with a duration of zero, so static and animated images
+    /// can be handled uniformly.
+    ///
+    /// Every frame is decoded up front, so rendering an
+    /// [`AnimatedImageView`] never waits for a frame to be decoded, and no
+    /// decoding happens on frame boundaries. The cost is that loading takes
+    /// longer, and all frames are kept in memory, so loading large
+    /// animations is best done off the thread drawing the terminal.
+    ///
+    /// The [`image`] crate does not expose the loop count stored in the
+    /// file, so the animation repeats forever, which is how most animations
+    /// are meant to be played. It can be changed with
+    /// [`set_loop_count`](AnimatedImage::set_loop_count).
+    pub fn load<R: BufRead + Seek>(mut r: R) -> Result<Self> {
+        let start = r.stream_position()?;
+        let format = image::io::Reader::new(&mut r)
+            .with_guessed_format()?
+            .format();
+        let frames: Option<Vec<image::Frame>> = match format {
+            #[cfg(feature = "gif")]
+            Some(image::ImageFormat::Gif) => {
+                use image::AnimationDecoder;
+                let decoder = image::codecs::gif::GifDecoder::new(&mut r)?;
+                Some(decoder.into_frames().collect_frames()?)
+            }
+            #[cfg(feature = "png")]
+            Some(image::ImageFormat::Png) => {
+                use image::AnimationDecoder;
+                let decoder = image::codecs::png::PngDecoder::new(&mut r)?;
+                if decoder.is_apng()? {
+                    Some(decoder.apng()?.into_frames().collect_frames()?)
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "webp")]
+            Some(image::ImageFormat::WebP) => {
+                use image::AnimationDecoder;
+                let decoder = image::codecs::webp::WebPDecoder::new(&mut r)?;
+                if decoder.has_animation() {
+                    Some(decoder.into_frames().collect_frames()?)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let frames = match frames {
+            Some(frames) => frames
+                .into_iter()
+                .map(|frame| {
+                    let delay = Duration::from(frame.delay());
+                    let image = Image {
+                        format,
+                        ..Image::new_rgba8(frame.buffer())
+                    };
+                    (image, delay)
+                })
+                .collect(),
+            None => {
+                r.seek(SeekFrom::Start(start))?;
+                vec![(Image::load(r)?, Duration::ZERO)]
+            }
+        };
+        Ok(Self::new(frames))
+    }
+
+    /// Opens an animation file from disk. The file format is automatically
+    /// detected from the content. See [`load`](AnimatedImage::load).
+    pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::load(std::io::BufReader::new(std::fs::File::open(path)?))
+    }
+
+    /// Factory pattern setter for the [`LoopCount`] of the animation
+    #[must_use = "method returns the modified value"]
+    pub fn with_loop_count(mut self, loop_count: LoopCount) -> Self {
+        self.set_loop_count(loop_count);
+        self
+    }
+
+    /// Setter for the [`LoopCount`] of the animation. Defaults to
+    /// [`LoopCount::Infinite`].
+    pub fn set_loop_count(&mut self, loop_count: LoopCount) {
+        self.loop_count = loop_count;
+    }
+
+    /// Gets the [`LoopCount`] of the animation
+    pub fn loop_count(&self) -> LoopCount {
+        self.loop_count
+    }
+
+    /// Gets the frames of the animation, in order, with the duration each is
+    /// shown
+    pub fn frames(&self) -> &[(Image, Duration)] {
+        &self.frames
+    }
+
+    /// Gets the number of frames of the animation
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Gets the duration of a single play of the animation, which is the sum
+    /// of the durations of its frames
+    pub fn total_duration(&self) -> Duration {
+        self.frames.iter().map(|(_, delay)| *delay).sum()
+    }
+
+    /// Gets the index of the frame shown after `elapsed` time since the start
+    /// of the animation, respecting the duration of each frame and the
+    /// [`LoopCount`]. Once a finite number of plays is over, the last frame
+    /// is shown. If the animation has no frames, or the durations of all
+    /// frames are zero, `0` is returned.
+    pub fn frame_at(&self, elapsed: Duration) -> usize {
+        let total = self.total_duration().as_nanos();
+        if total == 0 {
+            return 0;
+        }
+        let elapsed = elapsed.as_nanos();
+        if let LoopCount::Finite(count) = self.loop_count {
+            if elapsed >= total * count.max(1) as u128 {
+                return self.frames.len() - 1;
+            }
+        }
+        let mut offset = elapsed % total;
+        for (idx, (_, delay)) in self.frames.iter().enumerate() {
+            let delay = delay.as_nanos();
+            if offset < delay {
+                return idx;
+            }
+            offset -= delay;
+        }
+        self.frames.len() - 1
+    }
+}
+
+impl<'a> AnimatedImageView<'a> {
+    /// Creates a widget of the animation, rendering frames with the defaults
+    /// of [`Image::view`]
+    pub fn new(animation: &'a AnimatedImage) -> Self {
+        Self {
+            animation,
+            view: None,
+        }
+    }
+
+    /// Factory pattern setter for the render options of the frames, taken
+    /// from `view`, such as a view of the first frame. The image of `view`
+    /// is replaced with the current frame, and its [`Region`](crate::Region)
+    /// is clamped to the bounds of the frame.
+    #[must_use = "method returns the modified value"]
+    pub fn with_view(mut self, view: ImageView<'a>) -> Self {
+        self.view = Some(view);
+        self
+    }
+
+    /// Gets the animation
+    pub fn animation(&self) -> &'a AnimatedImage {
+        self.animation
+    }
+
+    /// Returns the view rendering `frame`, with the render options of the
+    /// widget
+    fn frame_view(&self, frame: &'a Image) -> ImageView<'a> {
+        let Some(options) = self.view else {
+            return frame.view();
+        };
+        let mut view = ImageView {
+            image: Some(frame),
+            pixels: &frame.pixels,
+            width: frame.width,
+            height: frame.height,
+            ..options
+        };
+        view.set_region(*options.region());
+        view
+    }
+}
+
+impl AnimatedImageState {
+    /// Creates a state at the start of the animation
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the time elapsed since the start of the animation by `delta`
+    pub fn tick(&mut self, delta: Duration) {
+        self.elapsed = self.elapsed.saturating_add(delta);
+    }
+
+    /// Rewinds to the start of the animation
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Gets the time elapsed since the start of the animation
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Gets the index of the frame selected by the last render
+    pub fn current(&self) -> usize {
+        self.current
+    }
+}
+
+impl<'a> StatefulWidget for AnimatedImageView<'a> {
+    type State = AnimatedImageState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        state.current = self.animation.frame_at(state.elapsed);
+        if let Some((frame, _)) = self.animation.frames.get(state.current) {
+            self.frame_view(frame).render(area, buf);
+        }
+    }
+}
+
+impl From<Image> for AnimatedImage {
+    /// Converts a static image into an animation of a single frame with a
+    /// duration of zero
+    fn from(image: Image) -> Self {
+        Self::new(vec![(image, Duration::ZERO)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pixel;
+
+    /// Builds an animation of solid 1x1 frames shown for the given numbers of
+    /// milliseconds, with the frame index in the red channel
+    fn animation(delays: &[u64]) -> AnimatedImage {
+        let frames = delays
+            .iter()
+            .enumerate()
+            .map(|(idx, &ms)| {
+                let pix = Pixel {
+                    r: idx as u8,
+                    g: 0,
+                    b: 0,
+                    a: 255,
+                };
+                (Image::solid(1, 1, pix), Duration::from_millis(ms))
+            })
+            .collect();
+        AnimatedImage::new(frames)
+    }
+
+    /// Advances `state` by `ms` milliseconds and renders `animation` with it,
+    /// returning the selected frame
+    fn tick(animation: &AnimatedImage, state: &mut AnimatedImageState, ms: u64) -> usize {
+        state.tick(Duration::from_millis(ms));
+        let area = Rect::new(0, 0, 1, 1);
+        let mut buf = Buffer::empty(area);
+        AnimatedImageView::new(animation).render(area, &mut buf, state);
+        state.current()
+    }
+
+    #[test]
+    fn tick_across_frame_boundaries() {
+        let animation = animation(&[100, 50, 200]);
+        let mut state = AnimatedImageState::new();
+        let steps = [
+            (0, 0),
+            (99, 0),
+            (1, 1),
+            (49, 1),
+            (1, 2),
+            (199, 2),
+            (1, 0),
+            (175, 2),
+        ];
+        for (ms, expected) in steps {
+            assert_eq!(
+                tick(&animation, &mut state, ms),
+                expected,
+                "{:?}",
+                state.elapsed()
+            );
+            assert_eq!(animation.frame_at(state.elapsed()), expected);
+        }
+        state.reset();
+        assert_eq!(tick(&animation, &mut state, 0), 0);
+    }
+
+    #[test]
+    fn finite_loop_stays_on_last_frame() {
+        let animation = animation(&[100, 100]).with_loop_count(LoopCount::Finite(2));
+        let mut state = AnimatedImageState::new();
+        assert_eq!(tick(&animation, &mut state, 250), 0);
+        assert_eq!(tick(&animation, &mut state, 100), 1);
+        assert_eq!(tick(&animation, &mut state, 50), 1);
+        assert_eq!(tick(&animation, &mut state, 10_000), 1);
+        // A count of zero plays once
+        let animation = animation.with_loop_count(LoopCount::Finite(0));
+        assert_eq!(animation.frame_at(Duration::from_millis(50)), 0);
+        assert_eq!(animation.frame_at(Duration::from_millis(250)), 1);
+    }
+
+    #[test]
+    fn zero_durations_show_first_frame() {
+        let animation = animation(&[0, 0, 0]);
+        let mut state = AnimatedImageState::new();
+        assert_eq!(tick(&animation, &mut state, 0), 0);
+        assert_eq!(tick(&animation, &mut state, 1_000), 0);
+        let animation = animation.with_loop_count(LoopCount::Finite(1));
+        assert_eq!(animation.frame_at(Duration::from_secs(5)), 0);
+        assert_eq!(AnimatedImage::default().frame_at(Duration::from_secs(5)), 0);
+    }
+}
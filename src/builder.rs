@@ -0,0 +1,134 @@
+use crate::{BorderMode, Image, LumaWeights, Pixel, Region};
+
+/// A builder chaining paint and filter operations to produce an [`Image`].
+///
+/// Each method applies the operation of the same name on [`Image`] and
+/// returns the builder, so procedurally generated images read top to bottom
+/// instead of interleaving mutations of a mutable [`Image`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ImageBuilder {
+    image: Image,
+}
+
+impl ImageBuilder {
+    /// Creates a builder starting from a solid black image of the provided
+    /// size (see [`Image::with_size`])
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::from_image(Image::with_size(width, height))
+    }
+
+    /// Creates a builder starting from an existing image
+    pub fn from_image(image: Image) -> Self {
+        Self { image }
+    }
+
+    /// Factory pattern setter applying [`Image::fill`]
+    #[must_use = "method returns the modified value"]
+    pub fn fill(mut self, pixel: Pixel) -> Self {
+        self.image.fill(pixel);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::fill_rect`]
+    #[must_use = "method returns the modified value"]
+    pub fn fill_rect(mut self, region: Region, pixel: Pixel) -> Self {
+        self.image.fill_rect(region, pixel);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::adjust_gamma`]
+    #[must_use = "method returns the modified value"]
+    pub fn adjust_gamma(mut self, gamma: f32) -> Self {
+        self.image.adjust_gamma(gamma);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::posterize`]
+    #[must_use = "method returns the modified value"]
+    pub fn posterize(mut self, levels: u8) -> Self {
+        self.image.posterize(levels);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::blur`]
+    #[must_use = "method returns the modified value"]
+    pub fn blur(mut self, sigma: f32) -> Self {
+        self.image.blur(sigma);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::sharpen`]
+    #[must_use = "method returns the modified value"]
+    pub fn sharpen(mut self, amount: f32) -> Self {
+        self.image.sharpen(amount);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::blur_with_border`]
+    #[must_use = "method returns the modified value"]
+    pub fn blur_with_border(mut self, sigma: f32, border: BorderMode) -> Self {
+        self.image.blur_with_border(sigma, border);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::sharpen_with_border`]
+    #[must_use = "method returns the modified value"]
+    pub fn sharpen_with_border(mut self, amount: f32, border: BorderMode) -> Self {
+        self.image.sharpen_with_border(amount, border);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::duotone`]
+    #[must_use = "method returns the modified value"]
+    pub fn duotone(mut self, shadow: Pixel, highlight: Pixel) -> Self {
+        self.image.duotone(shadow, highlight);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::grayscale`]
+    #[must_use = "method returns the modified value"]
+    pub fn grayscale(mut self, weights: LumaWeights) -> Self {
+        self.image.grayscale(weights);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::threshold`]
+    #[must_use = "method returns the modified value"]
+    pub fn threshold(mut self, level: u8, weights: LumaWeights) -> Self {
+        self.image.threshold(level, weights);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::vignette`]
+    #[must_use = "method returns the modified value"]
+    pub fn vignette(mut self, strength: f32) -> Self {
+        self.image.vignette(strength);
+        self
+    }
+
+    /// Factory pattern setter applying [`Image::chroma_key`]
+    #[must_use = "method returns the modified value"]
+    pub fn chroma_key(mut self, key: Pixel, tolerance: u8) -> Self {
+        self.image.chroma_key(key, tolerance);
+        self
+    }
+
+    /// Factory pattern setter applying an arbitrary operation on the image,
+    /// for steps without a dedicated method
+    #[must_use = "method returns the modified value"]
+    pub fn apply<F: FnOnce(&mut Image)>(mut self, op: F) -> Self {
+        op(&mut self.image);
+        self
+    }
+
+    /// Finishes the pipeline, returning the image
+    pub fn build(self) -> Image {
+        self.image
+    }
+}
+
+impl From<Image> for ImageBuilder {
+    fn from(image: Image) -> Self {
+        Self::from_image(image)
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    fill_area, fingerprint, BgColor, Checkerboard, ClipShape, ColorDepth, Cvd, Fit, ImageView,
+    LumaWeights, Protocol, Region, RenderMode, Resample, Rotation, SampleMode,
+};
+use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+use std::collections::{hash_map::DefaultHasher, HashMap};
+use std::hash::{Hash, Hasher};
+
+/// Everything that determines the rendered output of an [`ImageView`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CacheKey {
+    fingerprint: u64,
+    width: u16,
+    height: u16,
+    fit: Fit,
+    region: Region,
+    bg: BgColor,
+    checkerboard: Option<Checkerboard>,
+    clip_shape: ClipShape,
+    smooth_edges: bool,
+    transparency_passthrough: bool,
+    half_cell_offset: bool,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+    rotation: Rotation,
+    mode: RenderMode,
+    resample: Resample,
+    auto_resample: Option<u32>,
+    subpixel_offset: (u32, u32),
+    sample_mode: SampleMode,
+    cvd: Cvd,
+    luma_weights: LumaWeights,
+    ascii_ramp: u64,
+    color_depth: ColorDepth,
+    dither: bool,
+    protocol: Protocol,
+    font_size: (u16, u16),
+    border: Option<Color>,
+}
+
+/// Hashes the characters of an ASCII ramp, so the key does not have to own
+/// them
+fn ramp_hash(ramp: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    ramp.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    buf: Buffer,
+    last_used: u64,
+}
+
+/// A cache of rendered [`ImageView`]s, for applications switching between a
+/// handful of images (e.g. tabs or a thumbnail gallery).
+///
+/// Rendered cells are memoized by the content of the image (see
+/// [`Image::fingerprint`](crate::Image::fingerprint)), the size of the render
+/// area, and the settings of the view, such as its [`Fit`], [`Region`], and
+/// background color. Rendering a view that was already rendered with the same
+/// key copies the cached cells instead of scaling the image again. When the
+/// cache is full, the least recently used entry is evicted.
+///
+/// Views with clearing disabled (see [`ImageView::set_clear`]) depend on the
+/// existing content of the buffer, and views with a pixel hook (see
+/// [`ImageView::set_pixel_hook`]) may render differently on each frame, so they
+/// are always rendered directly.
+#[derive(Debug, Clone)]
+pub struct ImageCache {
+    capacity: usize,
+    entries: HashMap<CacheKey, CacheEntry>,
+    tick: u64,
+}
+
+impl ImageCache {
+    /// Creates an empty cache holding at most `capacity` rendered views
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Gets the maximum number of rendered views held by the cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Setter for the maximum number of rendered views held by the cache.
+    /// Least recently used entries are evicted if the cache holds more.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict(capacity);
+    }
+
+    /// The number of rendered views currently held by the cache
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether the cache holds no rendered views
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all rendered views from the cache
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Renders `view` into `area` of `buf`, reusing the cached cells if the
+    /// same view was already rendered into an area of the same size.
+    ///
+    /// This computes the fingerprint of the image on each call. Use
+    /// [`render_with_fingerprint`](ImageCache::render_with_fingerprint) to
+    /// avoid that for large images.
+    pub fn render(&mut self, view: &ImageView, area: Rect, buf: &mut Buffer) {
+        let fingerprint = fingerprint(view.pixels, view.width, view.height);
+        self.render_with_fingerprint(view, fingerprint, area, buf);
+    }
+
+    /// Renders `view` into `area` of `buf` like [`render`](ImageCache::render),
+    /// using a fingerprint of the image computed ahead of time with
+    /// [`Image::fingerprint`](crate::Image::fingerprint).
+    pub fn render_with_fingerprint(
+        &mut self,
+        view: &ImageView,
+        fingerprint: u64,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        if !view.clear || view.pixel_hook.is_some() || self.capacity == 0 {
+            view.render(area, buf);
+            return;
+        }
+        let key = CacheKey {
+            fingerprint,
+            width: area.width,
+            height: area.height,
+            fit: view.fit,
+            region: view.region,
+            bg: view.bg,
+            checkerboard: view.checkerboard,
+            clip_shape: view.clip_shape,
+            smooth_edges: view.smooth_edges,
+            transparency_passthrough: view.transparency_passthrough,
+            half_cell_offset: view.half_cell_offset,
+            flip_horizontal: view.flip_horizontal,
+            flip_vertical: view.flip_vertical,
+            rotation: view.rotation,
+            mode: view.mode,
+            resample: view.resample,
+            auto_resample: view.auto_resample,
+            subpixel_offset: view.subpixel_offset,
+            sample_mode: view.sample_mode,
+            cvd: view.cvd,
+            luma_weights: view.luma_weights,
+            ascii_ramp: ramp_hash(view.ascii_ramp),
+            color_depth: view.color_depth,
+            dither: view.dither,
+            protocol: view.protocol,
+            font_size: view.font_size,
+            border: view.border,
+        };
+        self.tick += 1;
+        if !self.entries.contains_key(&key) {
+            self.evict(self.capacity - 1);
+            let buf = view.render_to_buffer(area.width, area.height);
+            self.entries.insert(key, CacheEntry { buf, last_used: 0 });
+        }
+        let entry = self.entries.get_mut(&key).expect("entry was just inserted");
+        entry.last_used = self.tick;
+        let width = area.width as usize;
+        fill_area(area, buf, |x, y, cell| {
+            *cell = entry.buf.content[y as usize * width + x as usize].clone();
+        });
+    }
+
+    /// Evicts least recently used entries until at most `len` remain
+    fn evict(&mut self, len: usize) {
+        while self.entries.len() > len {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key);
+            match oldest {
+                Some(key) => self.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::ImageView;
+use ratatui::widgets::canvas::{Context, Painter, Shape};
+
+/// An [`ImageView`] placed at bounds in the coordinates of a canvas
+struct CanvasImage<'a> {
+    view: ImageView<'a>,
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+}
+
+impl<'a> ImageView<'a> {
+    /// Draws the view inside of a [`Canvas`](ratatui::widgets::canvas::Canvas)
+    /// context, stretched to fill the rectangle from `x_bounds[0]` to
+    /// `x_bounds[1]` and `y_bounds[0]` to `y_bounds[1]` in canvas coordinates.
+    ///
+    /// Each pixel is painted on the points of the canvas grid it covers, so the
+    /// image can be overlaid with other canvas shapes in the same coordinate
+    /// space. Like the canvas, the y-axis points up, but the image is drawn
+    /// upright. The [`Region`](crate::Region), [`Rotation`](crate::Rotation),
+    /// and flips of the view are applied, as well as its [`Cvd`](crate::Cvd)
+    /// simulation and [`ColorDepth`](crate::ColorDepth), and pixels are
+    /// blended with the background color, but the
+    /// [`Fit`](crate::Fit) mode, letterbox, and other render options are not
+    /// used. Fully transparent pixels are not painted.
+    ///
+    /// A canvas grid point only holds a single color per cell for most markers,
+    /// so [`Marker::HalfBlock`](ratatui::symbols::Marker::HalfBlock) gives the
+    /// most faithful result.
+    pub fn draw_on_canvas(&self, ctx: &mut Context, x_bounds: [f64; 2], y_bounds: [f64; 2]) {
+        ctx.draw(&CanvasImage {
+            view: *self,
+            x_bounds,
+            y_bounds,
+        });
+    }
+}
+
+impl<'a> Shape for CanvasImage<'a> {
+    fn draw(&self, painter: &mut Painter) {
+        let (width, height) = self.view.oriented_size();
+        if width == 0 || height == 0 {
+            return;
+        }
+        let [left, right] = self.x_bounds;
+        let [bottom, top] = self.y_bounds;
+        let step_x = (right - left) / width as f64;
+        let step_y = (top - bottom) / height as f64;
+        let depth = self.view.color_depth.resolve();
+        for y in 0..height {
+            for x in 0..width {
+                let Some(pix) = self.view.oriented_pixel(x, y) else {
+                    continue;
+                };
+                if pix.a == 0 {
+                    continue;
+                }
+                let color = depth.reduce(self.view.cvd.apply(pix).on(self.view.bg));
+                let x0 = left + x as f64 * step_x;
+                let y0 = top - y as f64 * step_y;
+                let corners = (
+                    painter.get_point(x0, y0),
+                    painter.get_point(x0 + step_x, y0 - step_y),
+                );
+                if let (Some((gx0, gy0)), Some((gx1, gy1))) = corners {
+                    for gy in gy0.min(gy1)..=gy0.max(gy1) {
+                        for gx in gx0.min(gx1)..=gx0.max(gx1) {
+                            painter.paint(gx, gy, color);
+                        }
+                    }
+                } else if let Some((gx, gy)) =
+                    painter.get_point(x0 + step_x / 2.0, y0 - step_y / 2.0)
+                {
+                    // The pixel is partially outside of the canvas
+                    painter.paint(gx, gy, color);
+                }
+            }
+        }
+    }
+}
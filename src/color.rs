@@ -0,0 +1,258 @@
+use crate::Pixel;
+use ratatui::style::Color;
+use std::sync::OnceLock;
+
+/// The colors a terminal can display, which the colors of rendered cells are
+/// reduced to. See [`ImageView::set_color_depth`](crate::ImageView::set_color_depth).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorDepth {
+    /// Detected from the environment with [`detect_color_depth`] the first
+    /// time a view with this depth is rendered. The result is kept for the
+    /// rest of the process.
+    Auto,
+    /// 24-bit RGB colors, used unchanged
+    #[default]
+    TrueColor,
+    /// The 256 color palette of xterm: the 16 system colors, a 6x6x6 color
+    /// cube, and a ramp of 24 grays. Colors are reduced to the nearest entry
+    /// of the cube or the gray ramp.
+    Ansi256,
+    /// The 16 system colors. Colors are reduced to the nearest of the colors
+    /// of the default xterm palette, but terminals may display them
+    /// differently according to their theme.
+    Ansi16,
+}
+
+/// The default xterm palette of the 16 system colors
+const ANSI16: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+/// Channel values of the levels of the 256 color cube
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Values of `TERM` of terminals known to only support the 16 system colors
+const ANSI16_TERMS: [&str; 8] = [
+    "dumb",
+    "linux",
+    "vt100",
+    "vt102",
+    "vt220",
+    "ansi",
+    "cons25",
+    "xterm-color",
+];
+
+/// The color depth detected for [`ColorDepth::Auto`]
+static DETECTED: OnceLock<ColorDepth> = OnceLock::new();
+
+/// Detects the color depth of the terminal from the `COLORTERM` and `TERM`
+/// environment variables. See [`detect_color_depth_with`].
+pub fn detect_color_depth() -> ColorDepth {
+    detect_color_depth_with(|name| std::env::var(name).ok())
+}
+
+/// Detects the color depth of the terminal from environment variables looked
+/// up with `var`, which allows injecting the environment e.g. in tests.
+///
+/// Most terminals in use support 24-bit colors, but many do not advertise it
+/// (e.g. `TERM=xterm-256color` without `COLORTERM`, or inside tmux), so the
+/// color depth is only reduced for terminals known to be limited.
+/// [`ColorDepth::TrueColor`] is chosen if `COLORTERM` is `truecolor` or
+/// `24bit`. Otherwise, [`ColorDepth::Ansi16`] is chosen if `TERM` is the name
+/// of a terminal only supporting the 16 system colors, such as `linux` (the
+/// Linux console), `vt100`, or `dumb`, or ends with `-16color` or `-8color`,
+/// and [`ColorDepth::TrueColor`] in every other case. [`ColorDepth::Auto`] is
+/// never returned.
+pub fn detect_color_depth_with<F: Fn(&str) -> Option<String>>(var: F) -> ColorDepth {
+    if let Some("truecolor" | "24bit") = var("COLORTERM").as_deref() {
+        return ColorDepth::TrueColor;
+    }
+    match var("TERM") {
+        Some(term)
+            if ANSI16_TERMS.contains(&term.as_str())
+                || term.ends_with("-16color")
+                || term.ends_with("-8color") =>
+        {
+            ColorDepth::Ansi16
+        }
+        _ => ColorDepth::TrueColor,
+    }
+}
+
+impl ColorDepth {
+    /// Resolves [`ColorDepth::Auto`] to the color depth detected from the
+    /// environment, which is only looked up once
+    pub(crate) fn resolve(self) -> ColorDepth {
+        match self {
+            ColorDepth::Auto => *DETECTED.get_or_init(detect_color_depth),
+            depth => depth,
+        }
+    }
+
+    /// Reduces an RGB color to the nearest color of the depth. Other colors
+    /// are returned unchanged.
+    pub(crate) fn reduce(self, color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return color;
+        };
+        match self {
+            ColorDepth::Auto | ColorDepth::TrueColor => color,
+            ColorDepth::Ansi256 => Color::Indexed(ansi256([r, g, b])),
+            ColorDepth::Ansi16 => ansi16([r, g, b]).0,
+        }
+    }
+
+    /// Gets the RGB value of the color an RGB color is reduced to by
+    /// [`reduce`](ColorDepth::reduce), according to the default xterm palette
+    fn nearest(self, rgb: [u8; 3]) -> [u8; 3] {
+        match self {
+            ColorDepth::Auto | ColorDepth::TrueColor => rgb,
+            ColorDepth::Ansi256 => ansi256_rgb(ansi256(rgb)),
+            ColorDepth::Ansi16 => ansi16(rgb).1,
+        }
+    }
+
+    /// Applies Floyd–Steinberg error diffusion to `width` by `height` pixels,
+    /// in row-major order, replacing each pixel with the nearest color of the
+    /// depth and spreading the quantization error to its unvisited
+    /// neighbors. This turns smooth gradients into a pattern of palette
+    /// colors whose average approximates the original colors, instead of
+    /// flat bands. Missing and fully transparent pixels are left unchanged,
+    /// and do not receive any error.
+    pub(crate) fn dither(self, pixels: &mut [Option<Pixel>], width: usize, height: usize) {
+        // Accumulated error of each channel, in sixteenths
+        let mut errors = vec![[0i32; 3]; pixels.len()];
+        for y in 0..height {
+            for x in 0..width {
+                let idx = y * width + x;
+                let Some(pix) = pixels[idx].as_mut().filter(|pix| pix.a != 0) else {
+                    continue;
+                };
+                let mut rgb = [pix.r, pix.g, pix.b];
+                for (val, err) in rgb.iter_mut().zip(errors[idx]) {
+                    *val = (*val as i32 + err / 16).clamp(0, 255) as u8;
+                }
+                let [r, g, b] = self.nearest(rgb);
+                (pix.r, pix.g, pix.b) = (r, g, b);
+                let err = [0, 1, 2].map(|ch| rgb[ch] as i32 - [r, g, b][ch] as i32);
+                let mut spread = |dx: isize, dy: usize, weight: i32| {
+                    let nx = x as isize + dx;
+                    if nx < 0 || nx >= width as isize || y + dy >= height {
+                        return;
+                    }
+                    let target = &mut errors[(y + dy) * width + nx as usize];
+                    for ch in 0..3 {
+                        target[ch] += err[ch] * weight;
+                    }
+                };
+                spread(1, 0, 7);
+                spread(-1, 1, 3);
+                spread(0, 1, 5);
+                spread(1, 1, 1);
+            }
+        }
+    }
+}
+
+/// Squared Euclidean distance between two RGB colors
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (*a as i32 - b as i32).pow(2) as u32)
+        .sum()
+}
+
+/// Finds the nearest of the 16 system colors, and its RGB value
+fn ansi16(rgb: [u8; 3]) -> (Color, [u8; 3]) {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, entry)| distance(*entry, rgb))
+        .copied()
+        .unwrap_or((Color::Black, [0; 3]))
+}
+
+/// Gets the RGB value of a color of the 256 color palette, among the color
+/// cube and the gray ramp
+fn ansi256_rgb(idx: u8) -> [u8; 3] {
+    match idx {
+        232.. => [8 + 10 * (idx - 232); 3],
+        _ => {
+            let idx = idx.saturating_sub(16);
+            [idx / 36, idx / 6 % 6, idx % 6].map(|level| CUBE_LEVELS[level as usize])
+        }
+    }
+}
+
+/// Finds the index of the nearest color of the 256 color palette, among the
+/// color cube (16 to 231) and the gray ramp (232 to 255)
+fn ansi256(rgb: [u8; 3]) -> u8 {
+    let level = |val: u8| match val {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (val - 35) / 40,
+    };
+    let cube = rgb.map(level);
+    let cube_rgb = cube.map(|idx| CUBE_LEVELS[idx as usize]);
+    let cube_idx = 16 + 36 * cube[0] + 6 * cube[1] + cube[2];
+
+    let mean = rgb.iter().map(|val| *val as u32).sum::<u32>() / 3;
+    let gray = (mean.saturating_sub(3) / 10).min(23) as u8;
+    let gray_val = 8 + 10 * gray;
+    if distance([gray_val; 3], rgb) < distance(cube_rgb, rgb) {
+        232 + gray
+    } else {
+        cube_idx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Detects the color depth of an environment with the given `COLORTERM`
+    /// and `TERM` variables
+    fn detect(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+        detect_color_depth_with(|name| match name {
+            "COLORTERM" => colorterm.map(str::to_owned),
+            "TERM" => term.map(str::to_owned),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn detect_only_downgrades_limited_terminals() {
+        for term in [
+            "xterm",
+            "xterm-256color",
+            "tmux-256color",
+            "screen-256color",
+            "alacritty",
+            "foot",
+            "wezterm",
+            "xterm-kitty",
+        ] {
+            assert_eq!(detect(None, Some(term)), ColorDepth::TrueColor, "{term}");
+        }
+        for term in ["linux", "vt100", "dumb", "xterm-16color", "rxvt-8color"] {
+            assert_eq!(detect(None, Some(term)), ColorDepth::Ansi16, "{term}");
+            assert_eq!(detect(Some("truecolor"), Some(term)), ColorDepth::TrueColor);
+        }
+        assert_eq!(detect(None, None), ColorDepth::TrueColor);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::Pixel;
+use std::sync::OnceLock;
+
+/// A color vision deficiency (color blindness) to simulate when rendering an
+/// [`ImageView`](crate::ImageView), to preview how its colors look to
+/// color-blind users. See [`ImageView::set_cvd`](crate::ImageView::set_cvd).
+///
+/// Each deficiency is simulated at full severity with the matrices of Machado,
+/// Oliveira, and Fernandes (2009), applied to linear RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Cvd {
+    /// No simulation, rendering colors unchanged
+    #[default]
+    None,
+    /// Missing red cones (red-green color blindness)
+    Protanopia,
+    /// Missing green cones (the most common red-green color blindness)
+    Deuteranopia,
+    /// Missing blue cones (blue-yellow color blindness)
+    Tritanopia,
+}
+
+const PROTANOPIA: [[f32; 3]; 3] = [
+    [0.152_286, 1.052_583, -0.204_868],
+    [0.114_503, 0.786_281, 0.099_216],
+    [-0.003_882, -0.048_116, 1.051_998],
+];
+
+const DEUTERANOPIA: [[f32; 3]; 3] = [
+    [0.367_322, 0.860_646, -0.227_968],
+    [0.280_085, 0.672_501, 0.047_413],
+    [-0.011_820, 0.042_940, 0.968_881],
+];
+
+const TRITANOPIA: [[f32; 3]; 3] = [
+    [1.255_528, -0.076_749, -0.178_779],
+    [-0.078_411, 0.930_809, 0.147_602],
+    [0.004_733, 0.691_367, 0.303_900],
+];
+
+impl Cvd {
+    /// Transforms the color of a pixel as seen with the deficiency. The alpha
+    /// channel is preserved.
+    pub(crate) fn apply(self, pix: Pixel) -> Pixel {
+        let matrix = match self {
+            Cvd::None => return pix,
+            Cvd::Protanopia => &PROTANOPIA,
+            Cvd::Deuteranopia => &DEUTERANOPIA,
+            Cvd::Tritanopia => &TRITANOPIA,
+        };
+        let table = decode_table();
+        let rgb = [
+            table[pix.r as usize],
+            table[pix.g as usize],
+            table[pix.b as usize],
+        ];
+        let row = |row: &[f32; 3]| encode(row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2]);
+        Pixel {
+            r: row(&matrix[0]),
+            g: row(&matrix[1]),
+            b: row(&matrix[2]),
+            a: pix.a,
+        }
+    }
+}
+
+/// Lookup table decoding sRGB channel values to linear values
+fn decode_table() -> &'static [f32; 256] {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0.0; 256];
+        for (val, out) in table.iter_mut().enumerate() {
+            let val = val as f32 / 255.0;
+            *out = if val <= 0.040_45 {
+                val / 12.92
+            } else {
+                ((val + 0.055) / 1.055).powf(2.4)
+            };
+        }
+        table
+    })
+}
+
+/// Encodes a linear channel value to sRGB
+fn encode(val: f32) -> u8 {
+    let val = val.clamp(0.0, 1.0);
+    let encoded = if val <= 0.003_130_8 {
+        val * 12.92
+    } else {
+        1.055 * val.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::io::ErrorKind;
+
+/// Error produced when loading an [`Image`](crate::Image)
+///
+/// The original error reported by the `image` crate is preserved, and is
+/// available through [`std::error::Error::source`]. For callers that only care
+/// about the kind of error, `Error` converts into [`std::io::Error`].
+#[derive(Debug)]
+pub enum Error {
+    /// The image data is malformed and could not be decoded
+    Decode(image::ImageError),
+    /// The image format, or a feature used by the image, is not supported
+    Unsupported(image::ImageError),
+    /// An I/O error occurred while reading the image
+    Io(std::io::Error),
+    /// Decoding the image would exceed the decoder's limits (e.g. the maximum
+    /// dimensions or memory usage)
+    LimitsExceeded(image::ImageError),
+    /// The image could not be decoded due to invalid parameters, such as
+    /// inconsistent dimensions
+    InvalidInput(image::ImageError),
+}
+
+/// Result type for fallible operations of this crate
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Decode(err) => write!(f, "failed to decode image: {}", err),
+            Error::Unsupported(err) => write!(f, "unsupported image: {}", err),
+            Error::Io(err) => write!(f, "failed to read image: {}", err),
+            Error::LimitsExceeded(err) => write!(f, "image exceeds decoding limits: {}", err),
+            Error::InvalidInput(err) => write!(f, "invalid image parameters: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Decode(err) => Some(err),
+            Error::Unsupported(err) => Some(err),
+            Error::Io(err) => Some(err),
+            Error::LimitsExceeded(err) => Some(err),
+            Error::InvalidInput(err) => Some(err),
+        }
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        use image::error::ImageError;
+
+        match err {
+            ImageError::Decoding(_) => Error::Decode(err),
+            ImageError::Encoding(_) => Error::Decode(err),
+            ImageError::Parameter(_) => Error::InvalidInput(err),
+            ImageError::Limits(_) => Error::LimitsExceeded(err),
+            ImageError::Unsupported(_) => Error::Unsupported(err),
+            ImageError::IoError(err) => Error::Io(err),
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        let kind = match err {
+            Error::Decode(_) => ErrorKind::InvalidData,
+            Error::Unsupported(_) => ErrorKind::Unsupported,
+            Error::Io(err) => return err,
+            Error::LimitsExceeded(_) => ErrorKind::InvalidData,
+            Error::InvalidInput(_) => ErrorKind::InvalidInput,
+        };
+        std::io::Error::new(kind, err)
+    }
+}
@@ -0,0 +1,652 @@
+use crate::{BorderMode, Image, LumaWeights, Pixel};
+use std::collections::HashMap;
+
+/// A box of colors in the median-cut algorithm, with the number of pixels of
+/// each color.
+struct ColorBox {
+    colors: Vec<([u8; 3], usize)>,
+}
+
+impl ColorBox {
+    fn range(&self, channel: usize) -> u8 {
+        let mut min = u8::MAX;
+        let mut max = u8::MIN;
+        for (color, _) in self.colors.iter() {
+            min = min.min(color[channel]);
+            max = max.max(color[channel]);
+        }
+        max.saturating_sub(min)
+    }
+
+    /// The channel with the largest range, and that range
+    fn widest(&self) -> (usize, u8) {
+        (0..3)
+            .map(|channel| (channel, self.range(channel)))
+            .max_by_key(|&(_, range)| range)
+            .unwrap_or((0, 0))
+    }
+
+    fn split(mut self) -> (ColorBox, ColorBox) {
+        let (channel, _) = self.widest();
+        self.colors.sort_unstable_by_key(|(color, _)| color[channel]);
+        let total: usize = self.colors.iter().map(|(_, count)| count).sum();
+        let mut seen = 0;
+        let mut at = 1;
+        for (idx, (_, count)) in self.colors.iter().enumerate() {
+            seen += count;
+            if seen * 2 >= total {
+                at = idx + 1;
+                break;
+            }
+        }
+        let at = at.clamp(1, self.colors.len() - 1);
+        let rest = self.colors.split_off(at);
+        (self, ColorBox { colors: rest })
+    }
+
+    fn average(&self) -> Pixel {
+        let mut sum = [0u64; 3];
+        let mut total = 0u64;
+        for (color, count) in self.colors.iter() {
+            for channel in 0..3 {
+                sum[channel] += color[channel] as u64 * *count as u64;
+            }
+            total += *count as u64;
+        }
+        let avg = |channel: usize| ((sum[channel] + total / 2) / total.max(1)) as u8;
+        Pixel {
+            r: avg(0),
+            g: avg(1),
+            b: avg(2),
+            a: 255,
+        }
+    }
+}
+
+impl BorderMode {
+    /// Maps an index along an axis of `len` pixels, possibly beyond its
+    /// edges, to the index of the pixel to sample, or [`None`] if the
+    /// constant color of [`BorderMode::Constant`] is sampled instead
+    fn index(self, idx: isize, len: isize) -> Option<isize> {
+        if (0..len).contains(&idx) {
+            return Some(idx);
+        }
+        match self {
+            BorderMode::Clamp => Some(idx.clamp(0, len - 1)),
+            BorderMode::Reflect => {
+                let idx = idx.rem_euclid(2 * len);
+                Some(if idx < len { idx } else { 2 * len - 1 - idx })
+            }
+            BorderMode::Wrap => Some(idx.rem_euclid(len)),
+            BorderMode::Constant(_) => None,
+        }
+    }
+}
+
+impl Image {
+    /// Reduces the image to at most `max_colors` distinct colors using the
+    /// median-cut algorithm, returning the result as a new image.
+    ///
+    /// Only the RGB channels are quantized. The alpha channel of every pixel is
+    /// preserved unchanged. A `max_colors` of `0` is treated as `1`. See also
+    /// [`quantize_with_palette`](Image::quantize_with_palette).
+    pub fn quantize(&self, max_colors: usize) -> Image {
+        self.quantize_with_palette(max_colors).0
+    }
+
+    /// Same as [`quantize`](Image::quantize), but also returns the reduced
+    /// palette.
+    ///
+    /// Every pixel of the returned image has the RGB value of one of the
+    /// palette entries. Palette entries are fully opaque.
+    pub fn quantize_with_palette(&self, max_colors: usize) -> (Image, Vec<Pixel>) {
+        let max_colors = max_colors.max(1);
+
+        let mut histogram = HashMap::new();
+        for pix in self.pixels.iter() {
+            *histogram.entry([pix.r, pix.g, pix.b]).or_insert(0usize) += 1;
+        }
+        if histogram.is_empty() {
+            return (self.clone(), Vec::new());
+        }
+
+        let mut boxes = vec![ColorBox {
+            colors: histogram.into_iter().collect(),
+        }];
+        while boxes.len() < max_colors {
+            let candidate = boxes
+                .iter()
+                .enumerate()
+                .filter(|(_, color_box)| color_box.colors.len() > 1)
+                .max_by_key(|(_, color_box)| color_box.widest().1)
+                .map(|(idx, _)| idx);
+            let Some(idx) = candidate else {
+                break;
+            };
+            let (first, second) = boxes.swap_remove(idx).split();
+            boxes.push(first);
+            boxes.push(second);
+        }
+
+        let palette: Vec<Pixel> = boxes.iter().map(ColorBox::average).collect();
+        let mut lookup = HashMap::new();
+        for (idx, color_box) in boxes.iter().enumerate() {
+            for (color, _) in color_box.colors.iter() {
+                lookup.insert(*color, idx);
+            }
+        }
+
+        let mut image = self.clone();
+        for pix in image.pixels.iter_mut() {
+            let entry = palette[lookup[&[pix.r, pix.g, pix.b]]];
+            pix.r = entry.r;
+            pix.g = entry.g;
+            pix.b = entry.b;
+        }
+        (image, palette)
+    }
+
+    /// Replaces the RGB channels of every pixel through a lookup table,
+    /// preserving alpha.
+    fn apply_lut(&mut self, lut: &[u8; 256]) {
+        for pix in self.pixels.iter_mut() {
+            pix.r = lut[pix.r as usize];
+            pix.g = lut[pix.g as usize];
+            pix.b = lut[pix.b as usize];
+        }
+    }
+
+    /// Applies gamma correction to the image, computing
+    /// `out = (in / 255) ^ (1 / gamma) * 255` for each color channel.
+    ///
+    /// A `gamma` greater than `1.0` brightens the image, and a `gamma` less than
+    /// `1.0` darkens it. A `gamma` of `1.0` leaves the image unchanged, as does
+    /// a `gamma` that is not a positive, finite number. The alpha channel is
+    /// preserved.
+    pub fn adjust_gamma(&mut self, gamma: f32) {
+        if !gamma.is_finite() || gamma <= 0.0 {
+            return;
+        }
+        let exp = 1.0 / gamma;
+        let mut lut = [0u8; 256];
+        for (val, out) in lut.iter_mut().enumerate() {
+            *out = ((val as f32 / 255.0).powf(exp) * 255.0)
+                .round()
+                .clamp(0.0, 255.0) as u8;
+        }
+        self.apply_lut(&lut);
+    }
+
+    /// Reduces each color channel to `levels` evenly spaced values, from 0 to
+    /// 255 inclusive, producing a flat, poster-like look.
+    ///
+    /// Each channel value is rounded to the nearest level. Since channels are
+    /// reduced independently, the image has at most `levels³` distinct colors.
+    /// For a palette chosen from the image content, see
+    /// [`quantize`](Image::quantize). The alpha channel is preserved. A
+    /// `levels` less than `2` is treated as `2`.
+    pub fn posterize(&mut self, levels: u8) {
+        let steps = levels.max(2) as u32 - 1;
+        let mut lut = [0u8; 256];
+        for (val, out) in lut.iter_mut().enumerate() {
+            let level = (val as u32 * steps + 127) / 255;
+            *out = ((level * 255 + steps / 2) / steps) as u8;
+        }
+        self.apply_lut(&lut);
+    }
+
+    /// Computes a Gaussian-blurred copy of the image as floating point RGBA
+    /// channels, using a separable kernel. Pixels beyond the edges of the image
+    /// are sampled according to `border`.
+    ///
+    /// The color channels are blurred premultiplied by alpha, so the colors of
+    /// transparent pixels do not bleed into their neighbors, and the result is
+    /// converted back to straight alpha. Fully transparent results are black.
+    fn gaussian(&self, sigma: f32, border: BorderMode) -> Vec<[f32; 4]> {
+        if !sigma.is_finite() || sigma <= 0.0 || self.pixels.is_empty() {
+            return self
+                .pixels
+                .iter()
+                .map(|pix| [pix.r as f32, pix.g as f32, pix.b as f32, pix.a as f32])
+                .collect();
+        }
+        let channels = |pix: &Pixel| {
+            let alpha = pix.a as f32 / 255.0;
+            [
+                pix.r as f32 * alpha,
+                pix.g as f32 * alpha,
+                pix.b as f32 * alpha,
+                pix.a as f32,
+            ]
+        };
+        let constant = match border {
+            BorderMode::Constant(pix) => channels(&pix),
+            _ => [0.0; 4],
+        };
+        let mut data: Vec<[f32; 4]> = self.pixels.iter().map(channels).collect();
+
+        let radius = (sigma * 3.0).ceil() as isize;
+        let mut kernel: Vec<f32> = (-radius..=radius)
+            .map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let sum: f32 = kernel.iter().sum();
+        kernel.iter_mut().for_each(|weight| *weight /= sum);
+
+        let (width, height) = (self.width as isize, self.height as isize);
+        let pass = |data: &[[f32; 4]], horizontal: bool| {
+            let mut out = vec![[0.0f32; 4]; data.len()];
+            for y in 0..height {
+                for x in 0..width {
+                    let mut acc = [0.0f32; 4];
+                    for (k, weight) in (-radius..=radius).zip(kernel.iter()) {
+                        let src = if horizontal {
+                            border.index(x + k, width).map(|sx| (sx, y))
+                        } else {
+                            border.index(y + k, height).map(|sy| (x, sy))
+                        };
+                        let src = match src {
+                            Some((sx, sy)) => data[(sy * width + sx) as usize],
+                            None => constant,
+                        };
+                        for (acc, src) in acc.iter_mut().zip(src) {
+                            *acc += src * weight;
+                        }
+                    }
+                    out[(y * width + x) as usize] = acc;
+                }
+            }
+            out
+        };
+        data = pass(&data, true);
+        let mut data = pass(&data, false);
+        for [r, g, b, a] in data.iter_mut() {
+            let scale = if *a > 0.0 { 255.0 / *a } else { 0.0 };
+            [*r, *g, *b] = [*r, *g, *b].map(|val| val * scale);
+        }
+        data
+    }
+
+    /// Blurs the image with a Gaussian kernel of standard deviation `sigma`,
+    /// in pixels.
+    ///
+    /// All channels, including alpha, are blurred, with the colors weighted by
+    /// alpha so that transparent pixels do not darken the edges of opaque
+    /// content. A `sigma` that is not a
+    /// positive, finite number leaves the image unchanged. Pixels beyond the
+    /// edges are clamped to the nearest edge pixel (see
+    /// [`blur_with_border`](Image::blur_with_border)).
+    pub fn blur(&mut self, sigma: f32) {
+        self.blur_with_border(sigma, BorderMode::Clamp);
+    }
+
+    /// Blurs the image like [`blur`](Image::blur), sampling pixels beyond the
+    /// edges of the image according to the given [`BorderMode`]
+    pub fn blur_with_border(&mut self, sigma: f32, border: BorderMode) {
+        self.alpha.invalidate();
+        let blurred = self.gaussian(sigma, border);
+        for (pix, src) in self.pixels.iter_mut().zip(blurred) {
+            let [r, g, b, a] = src.map(|val| val.round().clamp(0.0, 255.0) as u8);
+            *pix = Pixel { r, g, b, a };
+        }
+    }
+
+    /// Sharpens the image using an unsharp mask.
+    ///
+    /// A blurred copy of the image (see [`blur`](Image::blur)) is subtracted
+    /// from the image, and the difference, scaled by `amount`, is added back,
+    /// i.e. `out = in + amount * (in - blurred)`. This restores some of the
+    /// detail lost when downscaling an image for display. An `amount` of `0.0`
+    /// leaves the image unchanged, and typical values are between `0.5` and
+    /// `2.0`. The alpha channel is preserved. Pixels beyond the edges are
+    /// clamped to the nearest edge pixel (see
+    /// [`sharpen_with_border`](Image::sharpen_with_border)).
+    pub fn sharpen(&mut self, amount: f32) {
+        self.sharpen_with_border(amount, BorderMode::Clamp);
+    }
+
+    /// Sharpens the image like [`sharpen`](Image::sharpen), sampling pixels
+    /// beyond the edges of the image according to the given [`BorderMode`]
+    pub fn sharpen_with_border(&mut self, amount: f32, border: BorderMode) {
+        if amount == 0.0 || !amount.is_finite() {
+            return;
+        }
+        let blurred = self.gaussian(1.0, border);
+        let sharpen = |val: u8, blurred: f32| {
+            let val = val as f32;
+            (val + amount * (val - blurred)).round().clamp(0.0, 255.0) as u8
+        };
+        for (pix, src) in self.pixels.iter_mut().zip(blurred) {
+            pix.r = sharpen(pix.r, src[0]);
+            pix.g = sharpen(pix.g, src[1]);
+            pix.b = sharpen(pix.b, src[2]);
+        }
+    }
+
+    /// Recolors the image with two tones, mapping the luminance of each pixel
+    /// from `shadow` (black) to `highlight` (white).
+    ///
+    /// Intermediate luminance values are linearly interpolated between the two
+    /// tones with [`Pixel::blend`]. The alpha channel of each pixel is preserved,
+    /// and the alpha channels of `shadow` and `highlight` are ignored.
+    pub fn duotone(&mut self, shadow: Pixel, highlight: Pixel) {
+        for pix in self.pixels.iter_mut() {
+            let tone = shadow.blend(highlight, pix.luminance() as f32 / 255.0);
+            *pix = Pixel { a: pix.a, ..tone };
+        }
+    }
+
+    /// Converts the image to grayscale, replacing the color of each pixel with
+    /// its luminance computed with the given [`LumaWeights`]. The alpha
+    /// channel is preserved.
+    pub fn grayscale(&mut self, weights: LumaWeights) {
+        for pix in self.pixels.iter_mut() {
+            let lum = pix.luminance_with(weights);
+            *pix = Pixel {
+                r: lum,
+                g: lum,
+                b: lum,
+                a: pix.a,
+            };
+        }
+    }
+
+    /// Converts the image to black and white, making each pixel white if its
+    /// luminance, computed with the given [`LumaWeights`], is at least `level`,
+    /// and black otherwise. The alpha channel is preserved.
+    pub fn threshold(&mut self, level: u8, weights: LumaWeights) {
+        for pix in self.pixels.iter_mut() {
+            let val = if pix.luminance_with(weights) >= level {
+                255
+            } else {
+                0
+            };
+            *pix = Pixel {
+                r: val,
+                g: val,
+                b: val,
+                a: pix.a,
+            };
+        }
+    }
+
+    /// Darkens the image radially from the center outward.
+    ///
+    /// Each pixel is darkened according to the distance of its center from
+    /// the center of the image, normalized so that the corner pixels are at
+    /// distance 1, by `strength * distance²`. A `strength` of `0.0` leaves the
+    /// image unchanged, and a `strength` of `1.0` makes the corner pixels
+    /// black. The strength is clamped to that range. The alpha channel is
+    /// preserved.
+    pub fn vignette(&mut self, strength: f32) {
+        let strength = if strength.is_nan() {
+            0.0
+        } else {
+            strength.clamp(0.0, 1.0)
+        };
+        if strength == 0.0 {
+            return;
+        }
+        // Offset of a pixel center from the center of the image, relative to
+        // the offset of the outermost pixel centers
+        let offset = |pos: usize, len: usize| {
+            let half = (len as f32 - 1.0) / 2.0;
+            if half > 0.0 {
+                (pos as f32 - half) / half
+            } else {
+                0.0
+            }
+        };
+        for y in 0..self.height {
+            let dy = offset(y, self.height);
+            for x in 0..self.width {
+                let dx = offset(x, self.width);
+                let factor = 1.0 - strength * (dx * dx + dy * dy) / 2.0;
+                let pix = &mut self.pixels[(y * self.width) + x];
+                let darken = |val: u8| (val as f32 * factor).round().clamp(0.0, 255.0) as u8;
+                pix.r = darken(pix.r);
+                pix.g = darken(pix.g);
+                pix.b = darken(pix.b);
+            }
+        }
+    }
+
+    /// Makes every pixel within `tolerance` of the `key` color fully
+    /// transparent, such as for removing the solid background of a logo or
+    /// sprite (green screen).
+    ///
+    /// The tolerance is the largest Euclidean distance between the RGB
+    /// channels of a pixel and the key that is still removed, so a tolerance
+    /// of `0` only removes exact matches. The alpha channel of the key is
+    /// ignored. Only the alpha channel of removed pixels is changed, so their
+    /// colors are kept, e.g. for recovering soft edges later.
+    pub fn chroma_key(&mut self, key: Pixel, tolerance: u8) {
+        self.alpha.invalidate();
+        let max = tolerance as i32 * tolerance as i32;
+        let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        for pix in self.pixels.iter_mut() {
+            if d(pix.r, key.r) + d(pix.g, key.g) + d(pix.b, key.b) <= max {
+                pix.a = 0;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a 16x16 image covering a wide range of channel values,
+    /// including partially transparent pixels
+    fn sample_image() -> Image {
+        let data: Vec<u8> = (0..256)
+            .flat_map(|idx: usize| [idx, idx * 7, 255 - idx, idx * 3].map(|val| val as u8))
+            .collect();
+        Image::from_rgba_bytes(16, 16, &data).unwrap()
+    }
+
+    /// An opaque gray pixel
+    fn gray(val: u8) -> Pixel {
+        Pixel {
+            r: val,
+            g: val,
+            b: val,
+            a: 255,
+        }
+    }
+
+    #[test]
+    fn adjust_gamma_identity() {
+        let mut image = sample_image();
+        image.adjust_gamma(1.0);
+        assert_eq!(image.pixels(), sample_image().pixels());
+    }
+
+    /// Posterizes a single pixel of the given gray value
+    fn posterized(val: u8, levels: u8) -> u8 {
+        let mut image = Image::solid(
+            1,
+            1,
+            Pixel {
+                r: val,
+                g: val,
+                b: val,
+                a: 255,
+            },
+        );
+        image.posterize(levels);
+        image.pixels()[0].r
+    }
+
+    #[test]
+    fn posterize_boundaries() {
+        assert_eq!(posterized(0, 2), 0);
+        assert_eq!(posterized(127, 2), 0);
+        assert_eq!(posterized(128, 2), 255);
+        assert_eq!(posterized(255, 2), 255);
+        assert_eq!(posterized(63, 3), 0);
+        assert_eq!(posterized(64, 3), 128);
+        assert_eq!(posterized(191, 3), 128);
+        assert_eq!(posterized(192, 3), 255);
+        assert_eq!(posterized(0, 255), 0);
+        assert_eq!(posterized(255, 255), 255);
+    }
+
+    #[test]
+    fn posterize_clamps_levels() {
+        for val in [0, 127, 128, 255] {
+            assert_eq!(posterized(val, 0), posterized(val, 2));
+            assert_eq!(posterized(val, 1), posterized(val, 2));
+        }
+    }
+
+    #[test]
+    fn sharpen_zero_amount() {
+        let mut image = sample_image();
+        image.sharpen(0.0);
+        assert_eq!(image.pixels(), sample_image().pixels());
+        image.sharpen_with_border(0.0, BorderMode::Wrap);
+        assert_eq!(image.pixels(), sample_image().pixels());
+    }
+
+    #[test]
+    fn blur_without_transparent_halo() {
+        let red = Pixel {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        let clear = Pixel {
+            r: 0,
+            g: 0,
+            b: 255,
+            a: 0,
+        };
+        let mut image = Image::solid(6, 1, red);
+        image.pixels_mut()[3..].fill(clear);
+        image.blur(1.0);
+        for pix in image.pixels() {
+            assert_eq!((pix.r, pix.g, pix.b), (255, 0, 0));
+        }
+        assert!(image.pixels()[3].a > 0 && image.pixels()[2].a < 255);
+    }
+
+    #[test]
+    fn vignette_symmetry() {
+        let mut image = Image::solid(7, 5, gray(255));
+        image.vignette(0.6);
+        let pixel = |x, y| *image.pixel(x, y).unwrap();
+        assert_eq!(pixel(0, 0), pixel(6, 4));
+        assert_eq!(pixel(6, 0), pixel(0, 4));
+        assert_eq!(pixel(0, 0), pixel(6, 0));
+        assert_eq!(pixel(3, 2), gray(255));
+        assert!(pixel(0, 0).r < pixel(1, 1).r);
+        assert_eq!(pixel(0, 0).a, 255);
+    }
+
+    #[test]
+    fn vignette_full_strength() {
+        let mut image = Image::solid(4, 4, gray(255));
+        image.vignette(1.0);
+        for (x, y) in [(0, 0), (3, 0), (0, 3), (3, 3)] {
+            let pix = image.pixel(x, y).unwrap();
+            assert_eq!((pix.r, pix.g, pix.b, pix.a), (0, 0, 0, 255));
+        }
+    }
+
+    /// Applies a chroma key of pure green to a single opaque pixel, returning
+    /// the resulting pixel
+    fn keyed(r: u8, g: u8, b: u8, tolerance: u8) -> Pixel {
+        let mut image = Image::solid(1, 1, Pixel { r, g, b, a: 255 });
+        let key = Pixel {
+            r: 0,
+            g: 255,
+            b: 0,
+            a: 0,
+        };
+        image.chroma_key(key, tolerance);
+        image.pixels()[0]
+    }
+
+    #[test]
+    fn chroma_key_exact_match() {
+        assert_eq!(keyed(0, 255, 0, 0).a, 0);
+        assert_eq!(keyed(0, 254, 0, 0).a, 255);
+        assert_eq!(keyed(1, 255, 0, 0).a, 255);
+        let pix = keyed(0, 255, 0, 0);
+        assert_eq!((pix.r, pix.g, pix.b), (0, 255, 0));
+    }
+
+    #[test]
+    fn chroma_key_near_match() {
+        // Distance of exactly 5
+        assert_eq!(keyed(3, 251, 0, 5).a, 0);
+        assert_eq!(keyed(0, 250, 0, 5).a, 0);
+        // Distance just above 5
+        assert_eq!(keyed(3, 250, 0, 5).a, 255);
+        assert_eq!(keyed(0, 249, 0, 5).a, 255);
+        let pix = keyed(3, 251, 0, 5);
+        assert_eq!((pix.r, pix.g, pix.b), (3, 251, 0));
+    }
+
+    #[test]
+    fn border_mode_index() {
+        let len = 3;
+        for border in [BorderMode::Clamp, BorderMode::Reflect, BorderMode::Wrap] {
+            for idx in 0..len {
+                assert_eq!(border.index(idx, len), Some(idx));
+            }
+        }
+        let indices = |border: BorderMode| [-2, -1, len, len + 1].map(|idx| border.index(idx, len));
+        assert_eq!(
+            indices(BorderMode::Clamp),
+            [Some(0), Some(0), Some(2), Some(2)]
+        );
+        // cba|abc|cba
+        assert_eq!(
+            indices(BorderMode::Reflect),
+            [Some(1), Some(0), Some(2), Some(1)]
+        );
+        assert_eq!(
+            indices(BorderMode::Wrap),
+            [Some(1), Some(2), Some(0), Some(1)]
+        );
+        assert_eq!(indices(BorderMode::Constant(gray(0))), [None; 4]);
+    }
+
+    /// Blurs a single row of gray pixels with the given border mode, returning
+    /// the resulting gray values
+    fn blurred_row(values: &[u8], border: BorderMode) -> Vec<u8> {
+        let mut image = Image::solid(values.len(), 1, gray(0));
+        for (pix, val) in image.pixels_mut().iter_mut().zip(values) {
+            *pix = gray(*val);
+        }
+        image.blur_with_border(1.0, border);
+        image.pixels().iter().map(|pix| pix.r).collect()
+    }
+
+    #[test]
+    fn blur_single_row_borders() {
+        let row = [255, 0, 0, 0, 0, 0];
+        let clamp = blurred_row(&row, BorderMode::Clamp);
+        let reflect = blurred_row(&row, BorderMode::Reflect);
+        let wrap = blurred_row(&row, BorderMode::Wrap);
+        // Beyond the left edge: aaa (clamp), a00 (reflect), 000 (wrap)
+        assert!(clamp[0] > reflect[0]);
+        assert!(reflect[0] > wrap[0]);
+        // The right edge is next to the left edge when wrapping
+        assert_eq!(wrap[5], wrap[1]);
+        assert_eq!(clamp[5], reflect[5]);
+        assert!(wrap[5] > clamp[5]);
+
+        // Clamping keeps a uniform row uniform, while a constant black border
+        // darkens its edges (also vertically, since the row is a single pixel
+        // high)
+        assert_eq!(blurred_row(&[200; 6], BorderMode::Clamp), vec![200; 6]);
+        let constant = blurred_row(&[200; 6], BorderMode::Constant(gray(0)));
+        assert!(constant[0] < constant[2] && constant[5] < constant[3]);
+        assert!(constant[2] < 200);
+        let uniform = blurred_row(&[200; 6], BorderMode::Constant(gray(200)));
+        assert_eq!(uniform, vec![200; 6]);
+    }
+}
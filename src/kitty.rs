@@ -0,0 +1,658 @@
+use crate::{fingerprint, ImageView, Pixel};
+use ratatui::{buffer::Buffer, layout::Rect};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+/// Largest number of base64 bytes sent in a single graphics command
+const CHUNK_SIZE: usize = 4096;
+
+/// First placement id of [`KittyState`]s. Lower ids are left to the
+/// placements of [`KittyProtocol::render`], which are numbered per frame.
+const STATE_PLACEMENTS: u32 = 1 << 31;
+
+/// Placements of dropped [`KittyState`]s, by image id and placement id,
+/// shared between a protocol and the states it placed
+type Released = Arc<Mutex<Vec<(u32, u32)>>>;
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A renderer drawing [`ImageView`]s with the Kitty graphics protocol,
+/// supported by e.g. Kitty, Ghostty, WezTerm, and Konsole.
+///
+/// Each distinct rendered image (the pixels of a view at the size of its
+/// area) is transmitted to the terminal once, with an image id derived from a
+/// hash of its content. As long as it keeps being rendered, later frames only
+/// place the already transmitted image, which is cheap, and moving it to
+/// another area replaces the placement rather than adding one. Like with
+/// [`Protocol::Sixel`](crate::Protocol::Sixel), the escape sequences are
+/// written as the symbol of the top-left cell of the image, and the other
+/// cells covered by the image are marked as skipped, so they reach the
+/// terminal through the normal [`Buffer`] diffing of ratatui. The
+/// [`Protocol`](crate::Protocol) of the view is ignored, but its
+/// [`font_size`](ImageView::font_size) determines the resolution.
+///
+/// Images and placements stay on the screen until deleted, even when the
+/// cells below them are redrawn, so [`flush`](KittyProtocol::flush) must be
+/// called after each frame. It deletes images that were not rendered during
+/// the frame, by writing commands directly to the terminal, typically by
+/// passing `terminal.backend_mut()` right after `terminal.draw(...)`, since
+/// ratatui backends implement [`Write`].
+///
+/// For images that stay on screen across many frames, such as a large image
+/// in a viewer, [`render_stateful`](KittyProtocol::render_stateful) keeps a
+/// placement per [`KittyState`] instead, and only sends a command when the
+/// placement has to change.
+#[derive(Debug, Default)]
+pub struct KittyProtocol {
+    /// Ids of the transmitted images, by content
+    images: HashMap<u64, u32>,
+    /// Content of the transmitted images, by id
+    contents: HashMap<u32, u64>,
+    /// Number of placements of each image rendered during the current frame
+    drawn: HashMap<u32, u32>,
+    /// Number of placements of each image rendered during the last frame
+    previous: HashMap<u32, u32>,
+    /// Placements of [`KittyState`]s currently on the screen, by image id
+    /// and placement id
+    placed: HashSet<(u32, u32)>,
+    /// Next placement id to try for a [`KittyState`]
+    next_placement: u32,
+    /// Placements of dropped [`KittyState`]s, to delete on the next flush
+    released: Released,
+    /// Commands to write on the next flush
+    pending: String,
+}
+
+/// The placement of an image rendered with
+/// [`KittyProtocol::render_stateful`], kept across frames.
+///
+/// The state remembers which image was placed, and at which cell position
+/// and size, so rendering it again only sends a placement command when one
+/// of these changed. If the terminal scrolls the content of the screen (e.g.
+/// with an inline viewport), the placement moves with it, which is recorded
+/// with [`scroll`](KittyState::scroll). When the state is dropped, its
+/// placement is removed from the screen on the next
+/// [`flush`](KittyProtocol::flush).
+#[derive(Debug, Default)]
+pub struct KittyState {
+    placement: Option<Placement>,
+    /// Placements of dropped states of the protocol that placed this one
+    released: Option<Released>,
+}
+
+/// An image placed on the screen, with its top-left cell position and its
+/// size in cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    id: u32,
+    placement: u32,
+    x: i32,
+    y: i32,
+    cols: usize,
+    rows: usize,
+}
+
+impl KittyProtocol {
+    /// Creates a renderer without any transmitted images
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders `view` into `area` of `buf`, transmitting the image if it was
+    /// not transmitted before
+    pub fn render(&mut self, view: &ImageView, area: Rect, buf: &mut Buffer) {
+        let mapping = view.mapping(area);
+        view.render_graphics(&mapping, area, buf, |pixels, width, height| {
+            let pixels: Vec<Pixel> = pixels.iter().map(|pix| pix.unwrap_or_default()).collect();
+            let mut out = String::new();
+            let id = self.image_id(&mut out, &pixels, width, height);
+            let placement = self.drawn.entry(id).or_insert(0);
+            *placement += 1;
+            let cols = width / view.font_size.0.max(1) as usize;
+            let rows = height / view.font_size.1.max(1) as usize;
+            // Keep the cursor in place (C=1), and suppress responses (q=2) so
+            // they do not end up as input of the application
+            let _ = write!(
+                out,
+                "\x1b_Ga=p,i={},p={},c={},r={},C=1,q=2\x1b\\",
+                id, placement, cols, rows
+            );
+            out
+        });
+    }
+
+    /// Renders `view` into `area` of `buf` like
+    /// [`render`](KittyProtocol::render), keeping its placement in `state`.
+    ///
+    /// The image is transmitted if it was not transmitted before, and placed
+    /// if the state has no placement yet. Later renders send nothing as long
+    /// as the image, its position, and its size stay the same. If the
+    /// position or size changed, or the terminal scrolled (see
+    /// [`KittyState::scroll`]), the placement is updated without sending the
+    /// image again. The commands are written on the next
+    /// [`flush`](KittyProtocol::flush), and position the cursor at the
+    /// coordinates of `buf`, which are those of the terminal.
+    pub fn render_stateful(
+        &mut self,
+        view: &ImageView,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut KittyState,
+    ) {
+        state.released = Some(Arc::clone(&self.released));
+        let mapping = view.mapping(area);
+        let inner = view.image_area(area).intersection(buf.area);
+        let mut target = None;
+        view.render_graphics(&mapping, area, buf, |pixels, width, height| {
+            let pixels: Vec<Pixel> = pixels.iter().map(|pix| pix.unwrap_or_default()).collect();
+            let mut out = String::new();
+            let id = self.image_id(&mut out, &pixels, width, height);
+            self.pending.push_str(&out);
+            // Keep the image alive without adding a per-frame placement
+            self.drawn.entry(id).or_insert(0);
+            target = Some(Placement {
+                id,
+                placement: 0,
+                x: inner.x as i32,
+                y: inner.y as i32,
+                cols: width / view.font_size.0.max(1) as usize,
+                rows: height / view.font_size.1.max(1) as usize,
+            });
+            String::new()
+        });
+        let Some(mut target) = target else {
+            // Nothing of the image is visible
+            if let Some(last) = state.placement.take() {
+                self.remove_placement(last.id, last.placement);
+            }
+            return;
+        };
+        // The commands are written on flush, so the top-left cell is left
+        // alone like the other cells of the image
+        buf.get_mut(inner.x, inner.y).set_skip(true);
+
+        let last = state
+            .placement
+            .filter(|last| self.placed.contains(&(last.id, last.placement)));
+        target.placement = match last {
+            Some(last) if last.id == target.id => last.placement,
+            _ => 0,
+        };
+        if last == Some(target) {
+            return;
+        }
+        match last {
+            // Placing with the same ids replaces the placement
+            Some(last) if last.id == target.id => {}
+            Some(last) => {
+                self.remove_placement(last.id, last.placement);
+                target.placement = self.allocate_placement();
+            }
+            None => target.placement = self.allocate_placement(),
+        }
+        // Save and restore the cursor around the placement, keep the cursor
+        // in place (C=1), and suppress responses (q=2)
+        let _ = write!(
+            self.pending,
+            "\x1b7\x1b[{};{}H\x1b_Ga=p,i={},p={},c={},r={},C=1,q=2\x1b\\\x1b8",
+            target.y + 1,
+            target.x + 1,
+            target.id,
+            target.placement,
+            target.cols,
+            target.rows
+        );
+        self.placed.insert((target.id, target.placement));
+        state.placement = Some(target);
+    }
+
+    /// Writes the pending commands to `w`, and ends the frame: images that
+    /// were not rendered since the last flush are deleted from the terminal,
+    /// including their data, and extra placements of images rendered fewer
+    /// times than in the previous frame are removed.
+    ///
+    /// This should be called after each frame is drawn, typically with the
+    /// backend of the terminal as `w`.
+    pub fn flush<W: Write>(&mut self, w: &mut W) -> std::io::Result<()> {
+        let released =
+            std::mem::take(&mut *self.released.lock().unwrap_or_else(|err| err.into_inner()));
+        for (id, placement) in released {
+            self.remove_placement(id, placement);
+        }
+        for (id, count) in std::mem::take(&mut self.previous) {
+            match self.drawn.get(&id) {
+                None => self.delete(id),
+                Some(drawn) => {
+                    for placement in (drawn + 1)..=count {
+                        let _ = write!(
+                            self.pending,
+                            "\x1b_Ga=d,d=i,i={},p={},q=2\x1b\\",
+                            id, placement
+                        );
+                    }
+                }
+            }
+        }
+        self.previous = std::mem::take(&mut self.drawn);
+        w.write_all(self.pending.as_bytes())?;
+        self.pending.clear();
+        w.flush()
+    }
+
+    /// Deletes every transmitted image from the terminal on the next
+    /// [`flush`](KittyProtocol::flush), freeing their ids and the memory the
+    /// terminal uses for them, such as when the application exits or
+    /// switches screens. Images rendered afterwards are transmitted again.
+    pub fn delete_all(&mut self) {
+        let ids: Vec<u32> = self.contents.keys().copied().collect();
+        for id in ids {
+            self.delete(id);
+        }
+        self.drawn.clear();
+        self.previous.clear();
+        self.placed.clear();
+    }
+
+    /// Gets the number of images currently transmitted to the terminal
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Checks whether no image is currently transmitted to the terminal
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    /// Queues the deletion of an image, including its data (d=I), and frees
+    /// its id
+    fn delete(&mut self, id: u32) {
+        if let Some(key) = self.contents.remove(&id) {
+            self.images.remove(&key);
+        }
+        // Deleting the image also deletes its placements
+        self.placed.retain(|(placed, _)| *placed != id);
+        let _ = write!(self.pending, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", id);
+    }
+
+    /// Queues the deletion of a placement of a [`KittyState`], if it is still
+    /// on the screen
+    fn remove_placement(&mut self, id: u32, placement: u32) {
+        if self.placed.remove(&(id, placement)) {
+            let _ = write!(
+                self.pending,
+                "\x1b_Ga=d,d=i,i={},p={},q=2\x1b\\",
+                id, placement
+            );
+        }
+    }
+
+    /// Gets the id of the image with the given pixels, writing the commands
+    /// transmitting it to `out` if it was not transmitted before
+    fn image_id(&mut self, out: &mut String, pixels: &[Pixel], width: usize, height: usize) -> u32 {
+        let key = fingerprint(pixels, width, height);
+        match self.images.get(&key) {
+            Some(id) => *id,
+            None => {
+                let id = self.allocate(key);
+                transmit(out, id, pixels, width, height);
+                id
+            }
+        }
+    }
+
+    /// Allocates a placement id for a [`KittyState`] that is not used by any
+    /// other state
+    fn allocate_placement(&mut self) -> u32 {
+        loop {
+            let placement = self.next_placement.max(STATE_PLACEMENTS);
+            self.next_placement = placement.checked_add(1).unwrap_or(STATE_PLACEMENTS);
+            if !self.placed.iter().any(|(_, placed)| *placed == placement) {
+                return placement;
+            }
+        }
+    }
+
+    /// Allocates an id for the content `key`, derived from the key. If the
+    /// id is already used by other content, the next free id is used.
+    fn allocate(&mut self, key: u64) -> u32 {
+        // Ids are non-zero 32-bit numbers
+        let mut id = ((key >> 32) as u32 ^ key as u32).max(1);
+        while self.contents.contains_key(&id) {
+            id = id.checked_add(1).unwrap_or(1);
+        }
+        self.images.insert(key, id);
+        self.contents.insert(id, key);
+        id
+    }
+}
+
+impl KittyState {
+    /// Creates a state without a placement
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the terminal scrolled the content of the screen up by
+    /// `rows` (or down, if negative), moving the placement with it. The next
+    /// render moves the placement back to its area.
+    pub fn scroll(&mut self, rows: i32) {
+        if let Some(placement) = &mut self.placement {
+            placement.y -= rows;
+        }
+    }
+
+    /// Checks whether the state has a placement on the screen, as of the
+    /// last render
+    pub fn is_placed(&self) -> bool {
+        self.placement.is_some()
+    }
+}
+
+impl Drop for KittyState {
+    fn drop(&mut self) {
+        if let (Some(placement), Some(released)) = (self.placement, &self.released) {
+            let mut released = released.lock().unwrap_or_else(|err| err.into_inner());
+            released.push((placement.id, placement.placement));
+        }
+    }
+}
+
+/// Writes the commands transmitting the RGBA pixels of an image (a=t) to be
+/// placed later, split into chunks
+fn transmit(out: &mut String, id: u32, pixels: &[Pixel], width: usize, height: usize) {
+    let bytes: Vec<u8> = pixels
+        .iter()
+        .flat_map(|pix| [pix.r, pix.g, pix.b, pix.a])
+        .collect();
+    let data = base64(&bytes);
+    let mut chunks = data.as_bytes().chunks(CHUNK_SIZE).peekable();
+    let mut first = true;
+    while let Some(chunk) = chunks.next() {
+        let more = chunks.peek().is_some() as u8;
+        out.push_str("\x1b_G");
+        if first {
+            let _ = write!(out, "a=t,f=32,s={},v={},i={},q=2,", width, height, id);
+            first = false;
+        }
+        let _ = write!(out, "m={};", more);
+        // Chunks are ASCII, since base64 is
+        out.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        out.push_str("\x1b\\");
+    }
+}
+
+/// Encodes bytes as base64, with padding
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let val = chunk.iter().enumerate().fold(0u32, |val, (idx, byte)| {
+            val | (*byte as u32) << (16 - 8 * idx)
+        });
+        for idx in 0..4 {
+            if idx <= chunk.len() {
+                out.push(BASE64[(val >> (18 - 6 * idx)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Image;
+
+    /// Renders `image` into `area` of a buffer, returning the commands
+    /// written as the symbol of its top-left cell
+    fn render(kitty: &mut KittyProtocol, image: &Image, area: Rect) -> String {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 8));
+        kitty.render(&image.view(), area, &mut buf);
+        buf.get(area.x, area.y).symbol().to_owned()
+    }
+
+    /// Ends the frame, returning the commands written by the flush
+    fn flush(kitty: &mut KittyProtocol) -> String {
+        let mut out = Vec::new();
+        kitty.flush(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn image(val: u8) -> Image {
+        let pix = Pixel {
+            r: val,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        Image::solid(2, 2, pix)
+    }
+
+    #[test]
+    fn allocate_avoids_collisions() {
+        let mut kitty = KittyProtocol::new();
+        // Both keys fold to the same id
+        let first = kitty.allocate(1 << 32 | 2);
+        let second = kitty.allocate(2 << 32 | 1);
+        assert_eq!(first, 3);
+        assert_ne!(second, first);
+        assert_eq!(kitty.images[&(2 << 32 | 1)], second);
+        assert_eq!(kitty.contents[&second], 2 << 32 | 1);
+        // Zero is not a valid id
+        assert_ne!(kitty.allocate(0), 0);
+    }
+
+    #[test]
+    fn allocate_wraps_around() {
+        let mut kitty = KittyProtocol::new();
+        assert_eq!(kitty.allocate(u32::MAX as u64), u32::MAX);
+        assert_eq!(kitty.allocate((u32::MAX as u64) << 32), 1);
+        assert_eq!(kitty.allocate(u32::MAX as u64 * 3), 2);
+    }
+
+    #[test]
+    fn render_transmits_once() {
+        let mut kitty = KittyProtocol::new();
+        let area = Rect::new(0, 0, 2, 1);
+        let first = render(&mut kitty, &image(255), area);
+        assert!(first.starts_with("\x1b_Ga=t,f=32,"));
+        let id = kitty.images.values().next().copied().unwrap();
+        flush(&mut kitty);
+        let second = render(&mut kitty, &image(255), area);
+        assert_eq!(
+            second,
+            format!("\x1b_Ga=p,i={id},p=1,c=2,r=1,C=1,q=2\x1b\\")
+        );
+        assert_eq!(kitty.len(), 1);
+    }
+
+    #[test]
+    fn flush_deletes_images_not_drawn() {
+        let mut kitty = KittyProtocol::new();
+        let area = Rect::new(0, 0, 2, 1);
+        render(&mut kitty, &image(255), area);
+        flush(&mut kitty);
+        let kept = kitty.images.values().next().copied().unwrap();
+        render(&mut kitty, &image(255), area);
+        render(&mut kitty, &image(0), Rect::new(0, 2, 2, 1));
+        assert_eq!(flush(&mut kitty), "");
+        let removed = kitty
+            .contents
+            .keys()
+            .copied()
+            .find(|id| *id != kept)
+            .unwrap();
+        render(&mut kitty, &image(255), area);
+        let out = flush(&mut kitty);
+        assert_eq!(out, format!("\x1b_Ga=d,d=I,i={removed},q=2\x1b\\"));
+        assert_eq!(kitty.len(), 1);
+        assert!(kitty.contents.contains_key(&kept));
+    }
+
+    #[test]
+    fn flush_removes_extra_placements() {
+        let mut kitty = KittyProtocol::new();
+        render(&mut kitty, &image(255), Rect::new(0, 0, 2, 1));
+        let second = render(&mut kitty, &image(255), Rect::new(0, 2, 2, 1));
+        let id = kitty.images.values().next().copied().unwrap();
+        assert!(second.contains(&format!("i={id},p=2,")));
+        assert_eq!(flush(&mut kitty), "");
+        render(&mut kitty, &image(255), Rect::new(0, 0, 2, 1));
+        let out = flush(&mut kitty);
+        assert_eq!(out, format!("\x1b_Ga=d,d=i,i={id},p=2,q=2\x1b\\"));
+        assert_eq!(kitty.len(), 1);
+    }
+
+    #[test]
+    fn delete_all_frees_ids() {
+        let mut kitty = KittyProtocol::new();
+        let area = Rect::new(0, 0, 2, 1);
+        render(&mut kitty, &image(255), area);
+        render(&mut kitty, &image(0), Rect::new(0, 2, 2, 1));
+        flush(&mut kitty);
+        let ids: Vec<u32> = kitty.contents.keys().copied().collect();
+        kitty.delete_all();
+        assert!(kitty.is_empty());
+        assert!(kitty.contents.is_empty());
+        let out = flush(&mut kitty);
+        for id in ids {
+            assert!(out.contains(&format!("\x1b_Ga=d,d=I,i={id},q=2\x1b\\")));
+        }
+        // Nothing is left to delete on the next frame
+        assert_eq!(flush(&mut kitty), "");
+        assert!(render(&mut kitty, &image(255), area).starts_with("\x1b_Ga=t,"));
+    }
+
+    /// Renders `image` into `area` of a buffer with `state`, returning the
+    /// commands written by the following flush
+    fn render_stateful(
+        kitty: &mut KittyProtocol,
+        state: &mut KittyState,
+        image: &Image,
+        area: Rect,
+    ) -> String {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 8));
+        kitty.render_stateful(&image.view(), area, &mut buf, state);
+        assert!(!state.is_placed() || buf.get(area.x, area.y).skip);
+        flush(kitty)
+    }
+
+    /// The command placing image `id` as placement `placement` of 2x1 cells
+    /// at `x`, `y`
+    fn place(id: u32, placement: u32, x: u16, y: u16) -> String {
+        format!(
+            "\x1b7\x1b[{};{}H\x1b_Ga=p,i={id},p={placement},c=2,r=1,C=1,q=2\x1b\\\x1b8",
+            y + 1,
+            x + 1
+        )
+    }
+
+    #[test]
+    fn stateful_placement_diffing() {
+        let mut kitty = KittyProtocol::new();
+        let mut state = KittyState::new();
+        let area = Rect::new(1, 2, 2, 1);
+        let out = render_stateful(&mut kitty, &mut state, &image(255), area);
+        let id = kitty.images.values().next().copied().unwrap();
+        let first = place(id, STATE_PLACEMENTS, 1, 2);
+        assert!(out.starts_with("\x1b_Ga=t,f=32,"));
+        assert!(out.ends_with(&first));
+        assert!(state.is_placed());
+
+        // Unchanged
+        let out = render_stateful(&mut kitty, &mut state, &image(255), area);
+        assert_eq!(out, "");
+
+        // Moved, without retransmitting
+        let moved = Rect::new(4, 3, 2, 1);
+        let out = render_stateful(&mut kitty, &mut state, &image(255), moved);
+        assert_eq!(out, place(id, STATE_PLACEMENTS, 4, 3));
+
+        // Scrolled up with the content of the terminal, and placed back
+        state.scroll(2);
+        let out = render_stateful(&mut kitty, &mut state, &image(255), moved);
+        assert_eq!(out, place(id, STATE_PLACEMENTS, 4, 3));
+        let out = render_stateful(&mut kitty, &mut state, &image(255), moved);
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn stateful_image_change() {
+        let mut kitty = KittyProtocol::new();
+        let mut state = KittyState::new();
+        let area = Rect::new(0, 0, 2, 1);
+        render_stateful(&mut kitty, &mut state, &image(255), area);
+        let old = kitty.images.values().next().copied().unwrap();
+        let out = render_stateful(&mut kitty, &mut state, &image(0), area);
+        let new = kitty
+            .contents
+            .keys()
+            .copied()
+            .find(|id| *id != old)
+            .unwrap();
+        // The new image is transmitted and placed, the old placement removed,
+        // and the old image deleted since it was not drawn
+        let remove = format!("\x1b_Ga=d,d=i,i={old},p={STATE_PLACEMENTS},q=2\x1b\\");
+        let delete = format!("\x1b_Ga=d,d=I,i={old},q=2\x1b\\");
+        let place = place(new, STATE_PLACEMENTS + 1, 0, 0);
+        let remove_at = out.find(&remove).unwrap();
+        assert!(out.find(&place).unwrap() > remove_at);
+        assert!(out.ends_with(&delete));
+        assert_eq!(kitty.len(), 1);
+    }
+
+    #[test]
+    fn stateful_placement_out_of_view() {
+        let mut kitty = KittyProtocol::new();
+        let mut state = KittyState::new();
+        render_stateful(&mut kitty, &mut state, &image(255), Rect::new(0, 0, 2, 1));
+        let id = kitty.images.values().next().copied().unwrap();
+        let out = render_stateful(&mut kitty, &mut state, &image(255), Rect::new(9, 9, 2, 1));
+        let remove = format!("\x1b_Ga=d,d=i,i={id},p={STATE_PLACEMENTS},q=2\x1b\\");
+        assert!(out.starts_with(&remove));
+        assert!(!state.is_placed());
+    }
+
+    #[test]
+    fn stateful_placement_dropped() {
+        let mut kitty = KittyProtocol::new();
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 8));
+        let (first_area, second_area) = (Rect::new(0, 0, 2, 1), Rect::new(0, 2, 2, 1));
+        let image = image(255);
+        let mut first = KittyState::new();
+        let mut second = KittyState::new();
+        kitty.render_stateful(&image.view(), first_area, &mut buf, &mut first);
+        kitty.render_stateful(&image.view(), second_area, &mut buf, &mut second);
+        flush(&mut kitty);
+        let id = kitty.images.values().next().copied().unwrap();
+        let remove = |placement: u32| format!("\x1b_Ga=d,d=i,i={id},p={placement},q=2\x1b\\");
+
+        drop(first);
+        let out = render_stateful(&mut kitty, &mut second, &image, second_area);
+        assert_eq!(out, remove(STATE_PLACEMENTS));
+
+        drop(second);
+        let out = flush(&mut kitty);
+        let delete = format!("\x1b_Ga=d,d=I,i={id},q=2\x1b\\");
+        assert_eq!(out, remove(STATE_PLACEMENTS + 1) + &delete);
+        assert!(kitty.is_empty());
+    }
+
+    #[test]
+    fn stateful_placement_after_delete_all() {
+        let mut kitty = KittyProtocol::new();
+        let mut state = KittyState::new();
+        let area = Rect::new(0, 0, 2, 1);
+        render_stateful(&mut kitty, &mut state, &image(255), area);
+        kitty.delete_all();
+        flush(&mut kitty);
+        // The image and its placement are sent again
+        let out = render_stateful(&mut kitty, &mut state, &image(255), area);
+        let id = kitty.images.values().next().copied().unwrap();
+        assert!(out.starts_with("\x1b_Ga=t,f=32,"));
+        assert!(out.ends_with(&place(id, STATE_PLACEMENTS + 1, 0, 0)));
+    }
+}
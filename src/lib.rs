@@ -1,8 +1,67 @@
-use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
-use std::io::{BufRead, ErrorKind, Result, Seek};
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::{Margin, Rect},
+    style::Color,
+    symbols,
+    widgets::Widget,
+};
+use std::{
+    hash::{Hash, Hasher},
+    io::{BufRead, Read, Seek, SeekFrom},
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+mod animated;
+mod builder;
+mod cache;
+mod canvas;
+mod color;
+mod cvd;
+mod error;
+mod filter;
+mod kitty;
+mod metrics;
+mod mode;
+mod owned;
+mod resample;
+mod row;
+mod sixel;
+mod strips;
+#[cfg(feature = "interactive")]
+mod viewer;
+
+pub use animated::{AnimatedImage, AnimatedImageState, AnimatedImageView, LoopCount};
+pub use builder::ImageBuilder;
+pub use cache::ImageCache;
+pub use color::{detect_color_depth, detect_color_depth_with, ColorDepth};
+pub use cvd::Cvd;
+pub use error::{Error, Result};
+pub use kitty::{KittyProtocol, KittyState};
+pub use mode::RenderMode;
+pub use owned::OwnedImageView;
+pub use resample::Resample;
+pub use row::ImageRow;
+pub use strips::ImageStrips;
+#[cfg(feature = "interactive")]
+pub use viewer::{ViewState, Viewer};
+
+/// Re-exports of the most commonly used types, for glob importing with
+/// `use ratatui_image::prelude::*;`
+pub mod prelude {
+    pub use crate::{
+        BgColor, ColorDepth, Error, Fit, Image, ImageView, KittyProtocol, KittyState, Pixel,
+        Protocol, Region, RenderMode, Resample,
+    };
+}
 
 /// An image pixel color, represented as RGBA
+///
+/// The memory layout of this type is guaranteed: it is `repr(C)`, with a size
+/// of 4 bytes, an alignment of 1, no padding, and the channels stored in R, G,
+/// B, A order. A slice of pixels can therefore be reinterpreted as packed RGBA8
+/// bytes, as done by [`Image::as_rgba_bytes`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(C)]
 pub struct Pixel {
     /// Red channel
     pub r: u8,
@@ -14,12 +73,122 @@ pub struct Pixel {
     pub a: u8,
 }
 
-/// A single frame image, represented as a 2D array of RGBA pixels
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+const _: () = assert!(std::mem::size_of::<Pixel>() == 4);
+const _: () = assert!(std::mem::align_of::<Pixel>() == 1);
+
+/// A single frame image, represented as a 2D array of RGBA pixels.
+///
+/// Images compare and hash by their dimensions and pixels only. Metadata about
+/// the source, such as its bit depth, format, and ICC profile, is ignored, so
+/// a loaded image equals the same pixels built by other means.
+#[derive(Debug, Clone, Default)]
 pub struct Image {
     pixels: Vec<Pixel>,
     width: usize,
     height: usize,
+    depth: BitDepth,
+    format: Option<image::ImageFormat>,
+    icc_profile: Option<Vec<u8>>,
+    alpha: AlphaCache,
+}
+
+/// Lazily computed result of [`Image::has_alpha`], which is reset whenever
+/// the pixels may be modified. It is derived from the pixels, so it is ignored
+/// when comparing or hashing images.
+#[derive(Debug, Default)]
+struct AlphaCache(AtomicU8);
+
+impl AlphaCache {
+    const UNKNOWN: u8 = 0;
+    const OPAQUE: u8 = 1;
+    const TRANSPARENT: u8 = 2;
+
+    /// Gets the cached result, computing it from `pixels` if needed
+    fn get(&self, pixels: &[Pixel]) -> bool {
+        match self.0.load(Ordering::Relaxed) {
+            Self::OPAQUE => false,
+            Self::TRANSPARENT => true,
+            _ => {
+                let has_alpha = pixels.iter().any(|pix| pix.a < 255);
+                let state = if has_alpha {
+                    Self::TRANSPARENT
+                } else {
+                    Self::OPAQUE
+                };
+                self.0.store(state, Ordering::Relaxed);
+                has_alpha
+            }
+        }
+    }
+
+    /// Discards the cached result
+    fn invalidate(&mut self) {
+        *self.0.get_mut() = Self::UNKNOWN;
+    }
+}
+
+impl Clone for AlphaCache {
+    fn clone(&self) -> Self {
+        Self(AtomicU8::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
+impl PartialEq for Image {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.height == other.height && self.pixels == other.pixels
+    }
+}
+
+impl Eq for Image {}
+
+impl Hash for Image {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.pixels.hash(state);
+    }
+}
+
+/// Bit depth of the channels of the source an [`Image`] was decoded from.
+///
+/// An [`Image`] always stores 8 bits per channel, so sources with a higher
+/// depth lose precision when loaded. See [`Image::source_bit_depth`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BitDepth {
+    /// 8-bit integer channels. This is also reported for images created from
+    /// scratch rather than decoded.
+    #[default]
+    U8,
+    /// 16-bit integer channels, rounded to the nearest 8-bit value
+    U16,
+    /// 32-bit floating point channels, converted to 8 bits according to the
+    /// [`ToneMap`] used when loading
+    F32,
+}
+
+/// Tone-mapping operator converting floating point (e.g. HDR or OpenEXR)
+/// image data to 8 bits per channel when loading.
+///
+/// Floating point sources hold linear light values that may exceed `1.0`.
+/// The operators other than [`ToneMap::Clamp`] compress the full range of
+/// values into `0.0..=1.0` and encode the result to sRGB, so bright areas keep
+/// their detail instead of blowing out. The alpha channel is always clamped.
+///
+/// Floating point files may contain non-finite values. With every operator,
+/// NaN becomes 0, positive infinity becomes full intensity (255), and negative
+/// infinity becomes 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ToneMap {
+    /// Values are clamped to `0.0..=1.0` and scaled to 8 bits, without any
+    /// other conversion
+    #[default]
+    Clamp,
+    /// The Reinhard operator (`x / (1 + x)`), which smoothly compresses
+    /// highlights while leaving dark values nearly unchanged
+    Reinhard,
+    /// An approximation of the ACES filmic curve, with more contrast than
+    /// [`ToneMap::Reinhard`]
+    Aces,
 }
 
 /// Fit mode for rendering an [`ImageView`]
@@ -71,6 +240,149 @@ pub struct BgColor {
     pub b: u8,
 }
 
+/// A checkered background for rendering an [`ImageView`].
+///
+/// When set on an [`ImageView`], transparent pixels are blended with an
+/// alternating pattern of squares instead of a flat [`BgColor`], similar to
+/// how image editors display transparency. The squares are aligned to the
+/// render area rather than the image, so the pattern does not scale with
+/// the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Checkerboard {
+    /// The side length of each square, in pixels
+    pub size: usize,
+    /// Color of the square in the top-left corner, and every other square
+    pub c1: BgColor,
+    /// Color of the remaining squares
+    pub c2: BgColor,
+}
+
+/// How an [`ImageView`] is drawn in the terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Protocol {
+    /// Characters and colors of text cells, according to the [`RenderMode`]
+    /// of the view (half blocks by default). This works on any terminal.
+    #[default]
+    HalfBlock,
+    /// A Sixel graphics escape sequence, drawing the image at the full pixel
+    /// resolution of the terminal. This requires a terminal supporting Sixel,
+    /// such as xterm (with `-ti vt340`), foot, mlterm, or WezTerm. See
+    /// [`ImageView::set_protocol`].
+    Sixel,
+}
+
+/// Weights of the RGB channels when computing the luminance (brightness) of
+/// a pixel, such as with [`Pixel::luminance_with`].
+///
+/// The perceptually correct weights depend on the color space of the source,
+/// and technical imagery may be better served by weighting all channels the
+/// same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LumaWeights {
+    /// The Rec. 601 (SDTV) weights, `0.299 R + 0.587 G + 0.114 B`
+    #[default]
+    Rec601,
+    /// The Rec. 709 (HDTV and sRGB) weights, `0.2126 R + 0.7152 G + 0.0722 B`
+    Rec709,
+    /// The plain average of the channels, `(R + G + B) / 3`
+    Average,
+}
+
+/// How an [`ImageView`] samples positions of the render area beyond its
+/// [`Region`], such as the letterbox of [`Fit::Zoom`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SampleMode {
+    /// Positions beyond the region are not drawn, showing the letterbox
+    #[default]
+    None,
+    /// Positions beyond the region take the color of the nearest edge pixel,
+    /// extending the edges of the image
+    Clamp,
+    /// Positions beyond the region wrap around to the opposite edge, tiling
+    /// the image (e.g. for seamless textures)
+    Wrap,
+}
+
+/// How convolution filters such as [`Image::blur`] and [`Image::sharpen`]
+/// sample pixels beyond the edges of the image.
+///
+/// Different effects look best with different padding, e.g. [`Reflect`]
+/// avoids the edges of a blurred image from being pulled toward a single edge
+/// color.
+///
+/// [`Reflect`]: BorderMode::Reflect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BorderMode {
+    /// Pixels beyond the edges take the color of the nearest edge pixel
+    #[default]
+    Clamp,
+    /// Pixels beyond the edges mirror the image across the edge, with the
+    /// edge pixel repeated (`cba|abc`)
+    Reflect,
+    /// Pixels beyond the edges wrap around to the opposite edge
+    Wrap,
+    /// Pixels beyond the edges take the given color
+    Constant(Pixel),
+}
+
+/// Clockwise rotation applied when rendering an [`ImageView`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rotation {
+    /// No rotation
+    #[default]
+    None,
+    /// Rotated by 90 degrees clockwise
+    Clockwise90,
+    /// Rotated by 180 degrees
+    Clockwise180,
+    /// Rotated by 270 degrees clockwise (90 degrees counter-clockwise)
+    Clockwise270,
+}
+
+/// Shape of the mask applied when rendering an [`ImageView`].
+///
+/// The shape is fit to the bounds of the rendered image (excluding any
+/// letterbox), and rendered pixels falling outside of it are drawn as
+/// letterbox instead. The mask is evaluated at render time, so the [`Image`]
+/// is not modified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ClipShape {
+    /// The full rectangular bounds of the image are rendered
+    #[default]
+    Rectangle,
+    /// Only the ellipse inscribed in the image bounds is rendered. This is a
+    /// circle when the rendered image is square.
+    Circle,
+    /// The image bounds are rendered with rounded corners
+    RoundedRect {
+        /// The corner radius, in rendered pixels
+        radius: usize,
+    },
+}
+
+/// Easing function used by a [`RegionTween`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Easing {
+    /// Constant speed from start to end
+    #[default]
+    Linear,
+    /// Accelerates from the start and decelerates toward the end
+    EaseInOut,
+}
+
+/// Interpolates between two [`Region`]s over a duration.
+///
+/// This is intended for driving pan and zoom animations (e.g. a "Ken Burns"
+/// effect) on a still image: on each frame, pass the [`Region`] returned by
+/// [`region_at`](RegionTween::region_at) to [`ImageView::with_region`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RegionTween {
+    from: Region,
+    to: Region,
+    duration: std::time::Duration,
+    easing: Easing,
+}
+
 /// A renderable view of an image.
 ///
 /// An [`ImageView`] may represent only a specific region of the original
@@ -78,13 +390,106 @@ pub struct BgColor {
 /// the alpha channel, if any. See also [`Region`], [`Fit`], and [`BgColor`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ImageView<'a> {
-    image: &'a Image,
+    image: Option<&'a Image>,
+    pixels: &'a [Pixel],
+    width: usize,
+    height: usize,
     fit: Fit,
     region: Region,
     bg: BgColor,
+    checkerboard: Option<Checkerboard>,
+    clip_shape: ClipShape,
+    smooth_edges: bool,
+    clear: bool,
+    transparency_passthrough: bool,
+    half_cell_offset: bool,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+    rotation: Rotation,
+    mode: RenderMode,
+    resample: Resample,
+    auto_resample: Option<u32>,
+    subpixel_offset: (u32, u32),
+    sample_mode: SampleMode,
+    cvd: Cvd,
+    luma_weights: LumaWeights,
+    ascii_ramp: &'a str,
+    color_depth: ColorDepth,
+    dither: bool,
+    protocol: Protocol,
+    font_size: (u16, u16),
+    border: Option<Color>,
+    pixel_hook: Option<PixelHook<'a>>,
+}
+
+/// A callback applied to each pixel of an [`ImageView`] as it is sampled.
+/// See [`ImageView::set_pixel_hook`].
+pub type PixelHookFn = dyn Fn(usize, usize, Pixel) -> Pixel + Sync;
+
+/// A [`PixelHookFn`] reference, compared and hashed by address
+#[derive(Clone, Copy)]
+struct PixelHook<'a>(&'a PixelHookFn);
+
+impl<'a> std::fmt::Debug for PixelHook<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PixelHook")
+    }
+}
+
+impl<'a> PartialEq for PixelHook<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self.0, other.0)
+    }
+}
+
+impl<'a> Eq for PixelHook<'a> {}
+
+impl<'a> Hash for PixelHook<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.0 as *const PixelHookFn).cast::<()>().hash(state);
+    }
+}
+
+/// A widget rendering a weighted blend of two [`ImageView`]s, such as for a
+/// crossfade transition between images.
+///
+/// Both views are rendered into the same area, each according to its own
+/// [`Region`], [`Fit`], and background, and the resulting pixels are blended
+/// with [`Pixel::blend`]. Where only one of the views covers a pixel (e.g.
+/// where their letterboxes differ), that view's pixel is shown while its
+/// weight is at least one half, and the pixel is letterboxed otherwise.
+/// Letterbox cells are cleared unless clearing is disabled on both views (see
+/// [`ImageView::set_clear`]).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Crossfade<'a> {
+    from: ImageView<'a>,
+    to: ImageView<'a>,
+    t: f32,
+}
+
+/// Statistics about rendering an [`ImageView`], returned by
+/// [`ImageView::render_with_stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RenderStats {
+    /// The number of cells of the buffer that were written. Cells outside of
+    /// the buffer, and letterbox cells left untouched when clearing is
+    /// disabled, are not counted.
+    pub cells_written: usize,
+    /// Whether the exact-size path was taken (see [`ImageView::is_exact`])
+    pub exact: bool,
+    /// The effective horizontal zoom factor, in rendered half-block pixels per
+    /// image pixel. This is `0.0` if nothing of the image could be drawn.
+    pub zoom_x: f32,
+    /// The effective vertical zoom factor, in rendered half-block pixels per
+    /// image pixel. This is `0.0` if nothing of the image could be drawn.
+    pub zoom_y: f32,
+    /// The [`Resample`] filter used for scaling. Not used on the exact-size
+    /// path.
+    pub resample: Resample,
 }
 
-/// An iterator over the pixels of an [`ImageView`].
+/// An iterator over the pixels of an [`ImageView`], or of a [`Region`] of an
+/// [`Image`] (see [`Image::region_pixels`]).
 ///
 /// Pixels are ordered starting from the top-left pixel, left to right,
 /// then top to bottom (i.e. row by row).
@@ -97,23 +502,93 @@ pub struct ViewPixels<'a> {
     y: usize,
 }
 
+/// Computes the FNV-1a hash of the dimensions and pixels of an image. See
+/// [`Image::fingerprint`].
+fn fingerprint(pixels: &[Pixel], width: usize, height: usize) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET;
+    let mut feed = |byte: u8| {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    };
+    for byte in (width as u64)
+        .to_le_bytes()
+        .into_iter()
+        .chain((height as u64).to_le_bytes())
+    {
+        feed(byte);
+    }
+    for pix in pixels.iter() {
+        feed(pix.r);
+        feed(pix.g);
+        feed(pix.b);
+        feed(pix.a);
+    }
+    hash
+}
+
+/// The error for a buffer whose length does not match the given dimensions
+fn dimension_mismatch() -> Error {
+    Error::InvalidInput(image::ImageError::Parameter(
+        image::error::ParameterError::from_kind(image::error::ParameterErrorKind::DimensionMismatch),
+    ))
+}
+
+/// Scales a 16-bit channel to 8 bits, rounding to the nearest value
 fn u16_to_u8(value: u16) -> u8 {
-    (value >> 8) as u8
+    // 65535 / 255 == 257, so this is `value / 257` rounded half up
+    ((value as u32 + 128) / 257) as u8
 }
 
+/// Scales a floating point channel in `0.0..=1.0` to 8 bits, rounding to the
+/// nearest value. Out of range values are clamped, so positive infinity maps
+/// to 255 and negative infinity to 0, and NaN maps to 0.
 fn f32_to_u8(value: f32) -> u8 {
-    let value = value * 255.0;
-    if value < 0.0 {
-        0
-    } else if value > 255.0 {
-        255
-    } else {
-        value as u8
+    if value.is_nan() {
+        return 0;
+    }
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+impl ToneMap {
+    /// Converts a linear floating point color channel to 8 bits. NaN maps to
+    /// 0, positive infinity to 255, and negative infinity to 0 with every
+    /// operator.
+    fn apply(self, value: f32) -> u8 {
+        if value.is_nan() {
+            return 0;
+        }
+        let mapped = match self {
+            ToneMap::Clamp => return f32_to_u8(value),
+            // Both curves approach (or exceed) 1.0 for large values, but would
+            // compute infinity divided by infinity (NaN) if not capped.
+            ToneMap::Reinhard => {
+                let value = value.clamp(0.0, MAX_TONE_MAP_INPUT);
+                value / (1.0 + value)
+            }
+            ToneMap::Aces => {
+                let value = value.clamp(0.0, MAX_TONE_MAP_INPUT);
+                (value * (2.51 * value + 0.03)) / (value * (2.43 * value + 0.59) + 0.14)
+            }
+        };
+        // Tone-mapped values are linear, so they are encoded to sRGB for display
+        let encoded = if mapped <= 0.003_130_8 {
+            mapped * 12.92
+        } else {
+            1.055 * mapped.powf(1.0 / 2.4) - 0.055
+        };
+        (encoded.clamp(0.0, 1.0) * 255.0).round() as u8
     }
 }
 
+/// Largest input of the tone-mapping curves, far beyond where they round to
+/// full brightness, and small enough that squaring it does not overflow
+const MAX_TONE_MAP_INPUT: f32 = 1.0e6;
+
 impl Image {
-    fn new_gray8(im: image::GrayImage) -> Self {
+    fn new_gray8(im: &image::GrayImage) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -130,10 +605,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_grayalpha8(im: image::GrayAlphaImage) -> Self {
+    fn new_grayalpha8(im: &image::GrayAlphaImage) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -150,10 +629,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_rgb8(im: image::RgbImage) -> Self {
+    fn new_rgb8(im: &image::RgbImage) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -170,10 +653,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_rgba8(im: image::RgbaImage) -> Self {
+    fn new_rgba8(im: &image::RgbaImage) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -190,10 +677,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_gray16(im: image::ImageBuffer<image::Luma<u16>, Vec<u16>>) -> Self {
+    fn new_gray16(im: &image::ImageBuffer<image::Luma<u16>, Vec<u16>>) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -211,10 +702,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U16,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_grayalpha16(im: image::ImageBuffer<image::LumaA<u16>, Vec<u16>>) -> Self {
+    fn new_grayalpha16(im: &image::ImageBuffer<image::LumaA<u16>, Vec<u16>>) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -232,10 +727,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U16,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_rgb16(im: image::ImageBuffer<image::Rgb<u16>, Vec<u16>>) -> Self {
+    fn new_rgb16(im: &image::ImageBuffer<image::Rgb<u16>, Vec<u16>>) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -252,10 +751,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U16,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_rgba16(im: image::ImageBuffer<image::Rgba<u16>, Vec<u16>>) -> Self {
+    fn new_rgba16(im: &image::ImageBuffer<image::Rgba<u16>, Vec<u16>>) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
@@ -272,19 +775,23 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::U16,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_rgb32f(im: image::Rgb32FImage) -> Self {
+    fn new_rgb32f(im: &image::Rgb32FImage, tone_map: ToneMap) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
         let mut pixels = Vec::with_capacity(width * height);
         for pix in im.pixels() {
             pixels.push(Pixel {
-                r: f32_to_u8(pix.0[0]),
-                g: f32_to_u8(pix.0[1]),
-                b: f32_to_u8(pix.0[2]),
+                r: tone_map.apply(pix.0[0]),
+                g: tone_map.apply(pix.0[1]),
+                b: tone_map.apply(pix.0[2]),
                 a: 255,
             });
         }
@@ -292,19 +799,23 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::F32,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn new_rgba32f(im: image::Rgba32FImage) -> Self {
+    fn new_rgba32f(im: &image::Rgba32FImage, tone_map: ToneMap) -> Self {
         let (width, height) = im.dimensions();
         let width = width as usize;
         let height = height as usize;
         let mut pixels = Vec::with_capacity(width * height);
         for pix in im.pixels() {
             pixels.push(Pixel {
-                r: f32_to_u8(pix.0[0]),
-                g: f32_to_u8(pix.0[1]),
-                b: f32_to_u8(pix.0[2]),
+                r: tone_map.apply(pix.0[0]),
+                g: tone_map.apply(pix.0[1]),
+                b: tone_map.apply(pix.0[2]),
                 a: f32_to_u8(pix.0[3]),
             });
         }
@@ -312,10 +823,14 @@ impl Image {
             pixels,
             width,
             height,
+            depth: BitDepth::F32,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
         }
     }
 
-    fn from_image(im: image::DynamicImage) -> Self {
+    fn from_image(im: &image::DynamicImage, tone_map: ToneMap) -> Self {
         match im {
             image::DynamicImage::ImageLuma8(im) => Self::new_gray8(im),
             image::DynamicImage::ImageLumaA8(im) => Self::new_grayalpha8(im),
@@ -325,38 +840,129 @@ impl Image {
             image::DynamicImage::ImageLumaA16(im) => Self::new_grayalpha16(im),
             image::DynamicImage::ImageRgb16(im) => Self::new_rgb16(im),
             image::DynamicImage::ImageRgba16(im) => Self::new_rgba16(im),
-            image::DynamicImage::ImageRgb32F(im) => Self::new_rgb32f(im),
-            image::DynamicImage::ImageRgba32F(im) => Self::new_rgba32f(im),
-            _ => {
-                todo!()
-            }
+            image::DynamicImage::ImageRgb32F(im) => Self::new_rgb32f(im, tone_map),
+            image::DynamicImage::ImageRgba32F(im) => Self::new_rgba32f(im, tone_map),
+            im => Self::from_other(im),
         }
     }
 
-    fn from_reader<R: BufRead + Seek>(r: image::io::Reader<R>) -> Result<Self> {
-        use image::error::ImageError;
+    // `DynamicImage` is non-exhaustive, so variants without a dedicated
+    // constructor are converted to 8-bit RGBA first
+    fn from_other(im: &image::DynamicImage) -> Self {
+        Self::new_rgba8(&im.to_rgba8())
+    }
 
-        match r.decode() {
-            Ok(im) => Ok(Self::from_image(im)),
-            Err(ImageError::Decoding(_)) => Err(ErrorKind::InvalidData.into()),
-            Err(ImageError::Encoding(_)) => Err(ErrorKind::InvalidData.into()),
-            Err(ImageError::Parameter(_)) => Err(ErrorKind::InvalidInput.into()),
-            Err(ImageError::Limits(_)) => Err(ErrorKind::InvalidData.into()),
-            Err(ImageError::Unsupported(_)) => Err(ErrorKind::Unsupported.into()),
-            Err(ImageError::IoError(e)) => Err(e),
-        }
+    fn from_reader<R: BufRead + Seek>(r: image::io::Reader<R>, tone_map: ToneMap) -> Result<Self> {
+        let format = r.format();
+        // Decoding through the reader checks the size of the decoded image
+        // against its limits, which `DynamicImage::from_decoder` does not
+        let im = r.decode()?;
+        Ok(Self {
+            format,
+            ..Self::from_image(&im, tone_map)
+        })
+    }
+
+    /// Loads an image like [`from_reader`](Image::from_reader), then seeks
+    /// back to read the ICC profile with a decoder of the header only, since
+    /// decoding consumes the reader
+    fn from_stream<R: BufRead + Seek>(
+        mut r: R,
+        format: Option<image::ImageFormat>,
+        tone_map: ToneMap,
+    ) -> Result<Self> {
+        use image::ImageDecoder;
+
+        let start = r.stream_position()?;
+        let reader = match format {
+            Some(format) => image::io::Reader::with_format(&mut r, format),
+            None => image::io::Reader::new(&mut r).with_guessed_format()?,
+        };
+        let image = Self::from_reader(reader, tone_map)?;
+        let Some(format) = image.format else {
+            return Ok(image);
+        };
+        r.seek(SeekFrom::Start(start))?;
+        // A malformed profile is not worth failing over, since it is not used
+        // for decoding
+        let icc_profile = image::io::Reader::with_format(&mut r, format)
+            .into_decoder()
+            .ok()
+            .and_then(|mut decoder| decoder.icc_profile().ok().flatten());
+        Ok(Self {
+            icc_profile,
+            ..image
+        })
     }
 
     /// Loads an image from a type implementing [`BufRead`] and [`Seek`].
     /// The image format is automatically detected from the content.
     pub fn load<R: BufRead + Seek>(im: R) -> Result<Self> {
-        Self::from_reader(image::io::Reader::new(im).with_guessed_format()?)
+        Self::load_with_tone_map(im, ToneMap::Clamp)
+    }
+
+    /// Loads an image like [`load`](Image::load), converting floating point
+    /// (e.g. HDR or OpenEXR) sources to 8 bits with the given [`ToneMap`]
+    /// operator. Integer sources are not affected.
+    pub fn load_with_tone_map<R: BufRead + Seek>(im: R, tone_map: ToneMap) -> Result<Self> {
+        Self::from_stream(im, None, tone_map)
+    }
+
+    /// Loads an image of the given format from a type implementing [`Read`].
+    ///
+    /// Unlike [`load`](Image::load), the reader does not need to implement
+    /// [`Seek`], so this can be used with streams such as sockets, pipes, or
+    /// decompressors. The entire content is buffered into memory before
+    /// decoding, and since the format cannot be detected without seeking, it
+    /// must be known in advance.
+    pub fn load_with_format<R: Read>(mut r: R, format: image::ImageFormat) -> Result<Self> {
+        let mut data = Vec::new();
+        r.read_to_end(&mut data)?;
+        Self::from_stream(std::io::Cursor::new(data), Some(format), ToneMap::Clamp)
+    }
+
+    /// Loads an image from an already configured [`image::io::Reader`].
+    ///
+    /// This gives full control over decoding, such as setting
+    /// [`Limits`](image::io::Limits) or an explicit format, which
+    /// [`load`](Image::load) does not expose. Floating point sources are
+    /// converted with [`ToneMap::Clamp`], like [`load`](Image::load).
+    ///
+    /// Decoding consumes the reader, so unlike [`load`](Image::load), the
+    /// [`icc_profile`](Image::icc_profile) of the result is always [`None`].
+    pub fn from_image_reader<R: BufRead + Seek>(r: image::io::Reader<R>) -> Result<Self> {
+        Self::from_reader(r, ToneMap::Clamp)
+    }
+
+    /// Reads the dimensions (width, height) of an image from a type implementing
+    /// [`BufRead`] and [`Seek`], without decoding the image.
+    ///
+    /// Only the image header is read, which is much faster than [`load`](Image::load)
+    /// when only the size is needed (e.g. for layout, or for rejecting oversized
+    /// images). The image format is automatically detected from the content.
+    pub fn dimensions<R: BufRead + Seek>(r: R) -> Result<(usize, usize)> {
+        let (width, height) = image::io::Reader::new(r)
+            .with_guessed_format()?
+            .into_dimensions()?;
+        Ok((width as usize, height as usize))
     }
 
     /// Opens an image file from disk. The file format is automatially detected
     /// based on the path and the content.
     pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
-        Self::from_reader(image::io::Reader::open(path)?)
+        Self::open_with_tone_map(path, ToneMap::Clamp)
+    }
+
+    /// Opens an image file like [`open`](Image::open), converting floating
+    /// point (e.g. HDR or OpenEXR) sources to 8 bits with the given [`ToneMap`]
+    /// operator. Integer sources are not affected.
+    pub fn open_with_tone_map<P: AsRef<std::path::Path>>(
+        path: P,
+        tone_map: ToneMap,
+    ) -> Result<Self> {
+        let format = image::ImageFormat::from_path(&path).ok();
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        Self::from_stream(file, format, tone_map)
     }
 
     /// Creates a new image with the provided size.
@@ -379,29 +985,260 @@ impl Image {
             ],
             width,
             height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
+        }
+    }
+
+    /// Creates an image from raw bytes, with 4 bytes per pixel in R, G, B, A
+    /// order, such as from a camera, framebuffer, or FFI. Pixels are ordered
+    /// row by row, like [`pixels`](Image::pixels). See also
+    /// [`as_rgba_bytes`](Image::as_rgba_bytes).
+    ///
+    /// An [`Error::InvalidInput`] is returned if the length of `data` is not
+    /// `width * height * 4`.
+    pub fn from_rgba_bytes(width: usize, height: usize, data: &[u8]) -> Result<Self> {
+        Self::from_bytes(width, height, data, 4)
+    }
+
+    /// Creates a fully opaque image from raw bytes, with 3 bytes per pixel in
+    /// R, G, B order. Pixels are ordered row by row, like
+    /// [`pixels`](Image::pixels).
+    ///
+    /// An [`Error::InvalidInput`] is returned if the length of `data` is not
+    /// `width * height * 3`.
+    pub fn from_rgb_bytes(width: usize, height: usize, data: &[u8]) -> Result<Self> {
+        Self::from_bytes(width, height, data, 3)
+    }
+
+    /// Creates an image from raw RGB or RGBA bytes, with `channels` bytes per
+    /// pixel
+    fn from_bytes(width: usize, height: usize, data: &[u8], channels: usize) -> Result<Self> {
+        let len = width
+            .checked_mul(height)
+            .and_then(|len| len.checked_mul(channels));
+        if len != Some(data.len()) {
+            return Err(dimension_mismatch());
+        }
+        let pixels = data
+            .chunks_exact(channels)
+            .map(|pix| Pixel {
+                r: pix[0],
+                g: pix[1],
+                b: pix[2],
+                a: pix.get(3).copied().unwrap_or(255),
+            })
+            .collect();
+        Ok(Self {
+            pixels,
+            width,
+            height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
+        })
+    }
+
+    /// Creates a new image with the provided size, with all pixels set to `pixel`.
+    pub fn solid(width: usize, height: usize, pixel: Pixel) -> Self {
+        Self {
+            pixels: vec![pixel; width * height],
+            width,
+            height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
+        }
+    }
+
+    /// Creates a new image with the provided size, filled with a linear gradient
+    /// from `start` to `end`.
+    ///
+    /// If `vertical` is `true`, the gradient runs from the top row to the bottom
+    /// row. Otherwise, it runs from the left column to the right column. Colors
+    /// are interpolated with [`Pixel::blend`], including the alpha channel.
+    pub fn linear_gradient(
+        width: usize,
+        height: usize,
+        start: Pixel,
+        end: Pixel,
+        vertical: bool,
+    ) -> Self {
+        let steps = if vertical { height } else { width };
+        let step = |i: usize| {
+            if steps > 1 {
+                start.blend(end, i as f32 / (steps - 1) as f32)
+            } else {
+                start
+            }
+        };
+        let mut pixels = Vec::with_capacity(width * height);
+        if vertical {
+            for y in 0..height {
+                pixels.resize(pixels.len() + width, step(y));
+            }
+        } else {
+            pixels.extend((0..width).map(step));
+            for _ in 1..height {
+                pixels.extend_from_within(..width);
+            }
+        }
+        Self {
+            pixels,
+            width,
+            height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
+        }
+    }
+
+    /// Creates a new image with the provided size, filled with a checkerboard
+    /// pattern of `size` by `size` pixel squares.
+    ///
+    /// The top-left square is `c1`, and squares alternate between `c2` and `c1`
+    /// along both axes. A `size` of `0` is treated as `1`.
+    pub fn checkerboard(width: usize, height: usize, size: usize, c1: Pixel, c2: Pixel) -> Self {
+        let size = size.max(1);
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                if ((x / size) + (y / size)) % 2 == 0 {
+                    pixels.push(c1);
+                } else {
+                    pixels.push(c2);
+                }
+            }
+        }
+        Self {
+            pixels,
+            width,
+            height,
+            depth: BitDepth::U8,
+            format: None,
+            icc_profile: None,
+            alpha: AlphaCache::default(),
+        }
+    }
+
+    /// Sets every pixel of the image to `pixel`
+    pub fn fill(&mut self, pixel: Pixel) {
+        self.alpha.invalidate();
+        self.pixels.fill(pixel);
+    }
+
+    /// Sets every pixel within `region` to `pixel`. The region is clamped to
+    /// the bounds of the image.
+    pub fn fill_rect(&mut self, region: Region, pixel: Pixel) {
+        self.alpha.invalidate();
+        let region = region.clamped(self.width, self.height);
+        for y in region.y..(region.y + region.height) {
+            let start = (y * self.width) + region.x;
+            self.pixels[start..(start + region.width)].fill(pixel);
         }
     }
 
+    /// The bit depth of the source the image was decoded from.
+    ///
+    /// Pixels are always stored with 8 bits per channel, so anything other
+    /// than [`BitDepth::U8`] means precision was reduced when loading. Images
+    /// derived from another image (e.g. by cloning, cropping, or filtering)
+    /// keep the depth of the original.
+    pub const fn source_bit_depth(&self) -> BitDepth {
+        self.depth
+    }
+
+    /// The ICC color profile embedded in the file or data the image was
+    /// decoded from, if any.
+    ///
+    /// Pixels are always loaded as is and rendered as sRGB, so an image with a
+    /// profile for a different color space (e.g. Display P3 or Adobe RGB) may
+    /// render with shifted colors. The profile can be used to warn about that,
+    /// or to convert the pixels with a color management library. Note that
+    /// many images embed an sRGB profile, which needs no conversion. The
+    /// profile is not read by [`from_image_reader`](Image::from_image_reader).
+    /// Like [`source_format`](Image::source_format), images derived from a
+    /// loaded image keep its profile.
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        self.icc_profile.as_deref()
+    }
+
+    /// Checks whether precision was reduced when the image was loaded, i.e.
+    /// whether its source had more than 8 bits per channel (see
+    /// [`source_bit_depth`](Image::source_bit_depth)).
+    ///
+    /// Loading never fails or warns because of the reduction, so applications
+    /// where exact values matter (e.g. scientific or medical imaging) can check
+    /// this after loading to warn the user or reject the image.
+    pub const fn precision_reduced(&self) -> bool {
+        !matches!(self.depth, BitDepth::U8)
+    }
+
+    /// The format of the file or data the image was decoded from, or [`None`]
+    /// if the image was created from scratch rather than loaded.
+    ///
+    /// This can be used to tell whether e.g. a PNG (where alpha is likely
+    /// meaningful) or a JPEG (without alpha) was loaded. Like
+    /// [`source_bit_depth`](Image::source_bit_depth), images derived from a
+    /// loaded image keep its format.
+    pub const fn source_format(&self) -> Option<image::ImageFormat> {
+        self.format
+    }
+
     /// The width of the image, in pixels
-    pub fn width(&self) -> usize {
+    pub const fn width(&self) -> usize {
         self.width
     }
 
     /// The height of the image, in pixels
-    pub fn height(&self) -> usize {
+    pub const fn height(&self) -> usize {
         self.height
     }
 
     /// The width of the image, in terminal cells
-    pub fn cell_width(&self) -> usize {
+    pub const fn cell_width(&self) -> usize {
         self.width
     }
 
-    /// The height of the image, in terminal cells, rounded up
-    pub fn cell_height(&self) -> usize {
+    /// The height of the image, in terminal cells, rounded up. With an odd
+    /// height, the last cell is only half filled. See also
+    /// [`cell_height_floor`](Image::cell_height_floor).
+    pub const fn cell_height(&self) -> usize {
         self.height / 2 + self.height % 2
     }
 
+    /// The height of the image, in terminal cells, rounded down, i.e. the
+    /// number of cells completely filled by the image. This is useful for
+    /// packing images vertically without a gap after a half filled cell.
+    pub const fn cell_height_floor(&self) -> usize {
+        self.height / 2
+    }
+
+    /// The `(width, height)` of the area, in terminal cells, needed to render
+    /// the image at its native size, with each pixel in half of a cell.
+    ///
+    /// Since each cell holds two vertically stacked pixels, the height is half
+    /// the image height, rounded up. Rendering an [`ImageView`] of the whole
+    /// image into an area of this size takes the exact-size path (see
+    /// [`ImageView::is_exact`]). An image with an odd height additionally
+    /// needs [`ImageView::set_half_cell_offset`] for that. Both values
+    /// are clamped to [`u16::MAX`], so they can be used directly for layout
+    /// constraints.
+    pub fn cell_area(&self) -> (u16, u16) {
+        let clamp = |cells: usize| cells.min(u16::MAX as usize) as u16;
+        (clamp(self.cell_width()), clamp(self.cell_height()))
+    }
+
+    /// Checks whether the pixel coordinates are within the bounds of the image
+    pub const fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
     /// The pixels of the image.
     ///
     /// Pixels are ordered starting from the top-left pixel, left to right,
@@ -413,11 +1250,39 @@ impl Image {
     /// The pixels of the image.
     ///
     /// Pixels are ordered starting from the top-left pixel, left to right,
-    /// then top to bottom (i.e. row by row).
+    /// then top to bottom (i.e. row by row). This discards the cached result
+    /// of [`has_alpha`](Image::has_alpha), which is recomputed on its next
+    /// call.
     pub fn pixels_mut(&mut self) -> &mut [Pixel] {
+        self.alpha.invalidate();
         &mut self.pixels[..]
     }
 
+    /// The pixels of the image as raw bytes, with 4 bytes per pixel in R, G, B,
+    /// A order.
+    ///
+    /// Pixels are ordered the same as [`pixels`](Image::pixels). No copy is
+    /// made, so this is suitable for handing the image data to encoders or
+    /// graphics APIs expecting packed RGBA8 data.
+    pub fn as_rgba_bytes(&self) -> &[u8] {
+        // SAFETY: `Pixel` is `repr(C)` with four `u8` fields, so it has a size
+        // of 4, an alignment of 1, no padding, and its fields are laid out in
+        // declaration order (R, G, B, A).
+        unsafe { std::slice::from_raw_parts(self.pixels.as_ptr().cast(), self.pixels.len() * 4) }
+    }
+
+    /// Converts the image into its pixels as raw bytes, with 4 bytes per pixel
+    /// in R, G, B, A order, without copying. See [`as_rgba_bytes`](Image::as_rgba_bytes).
+    pub fn into_rgba_bytes(self) -> Vec<u8> {
+        let mut pixels = std::mem::ManuallyDrop::new(self.pixels);
+        let (ptr, len, cap) = (pixels.as_mut_ptr(), pixels.len(), pixels.capacity());
+        // SAFETY: `Pixel` is `repr(C)` with four `u8` fields, so it has the same
+        // alignment as `u8` and exactly 4 times its size. The allocation is
+        // therefore valid for a `Vec<u8>` with 4 times the length and capacity,
+        // and ownership of it is transferred from the forgotten `Vec<Pixel>`.
+        unsafe { Vec::from_raw_parts(ptr.cast(), len * 4, cap * 4) }
+    }
+
     /// Gets the pixel value at given pixel coordinates. [`None`](std::option::Option)
     /// is returned if the coordinates are out of bounds.
     pub fn pixel(&self, x: usize, y: usize) -> Option<&Pixel> {
@@ -429,11 +1294,14 @@ impl Image {
     }
 
     /// Gets the pixel value at given pixel coordinates. [`None`](std::option::Option)
-    /// is returned if the coordinates are out of bounds.
+    /// is returned if the coordinates are out of bounds. Like
+    /// [`pixels_mut`](Image::pixels_mut), this discards the cached result of
+    /// [`has_alpha`](Image::has_alpha).
     pub fn pixel_mut(&mut self, x: usize, y: usize) -> Option<&mut Pixel> {
         if x >= self.width || y >= self.height {
             None
         } else {
+            self.alpha.invalidate();
             Some(&mut self.pixels[(y * self.width) + x])
         }
     }
@@ -442,54 +1310,406 @@ impl Image {
     ///
     /// The returned [`ImageView`] defaults to [`Fit::Zoom`] and black background
     /// color (`#000000`).
+    #[must_use]
     pub fn view(&self) -> ImageView<'_> {
         ImageView::new(self)
     }
-}
 
-impl Region {
-    /// The X-coordinate (horizontal) of the top-left pixel in terms of terminal cells
-    pub fn cell_x(&self) -> usize {
-        self.x
+    /// Returns an iterator over the pixels of a [`Region`] of the image,
+    /// without creating an [`ImageView`], such as for processing the image in
+    /// tiles. The region is clamped to the bounds of the image.
+    pub fn region_pixels(&self, region: Region) -> ViewPixels<'_> {
+        let region = region.clamped(self.width, self.height);
+        ViewPixels {
+            pixels: &self.pixels,
+            region,
+            real_width: self.width,
+            x: region.x,
+            y: region.y,
+        }
     }
 
-    /// The Y-coordinate (vertical) of the top-left pixel in terms of terminal cells, rounded up
-    pub fn cell_y(&self) -> usize {
-        self.y / 2 + self.y % 2
+    fn copy_region(&self, region: Region) -> Self {
+        let mut pixels = Vec::with_capacity(region.width * region.height);
+        for y in region.y..(region.y + region.height) {
+            let start = (y * self.width) + region.x;
+            pixels.extend_from_slice(&self.pixels[start..(start + region.width)]);
+        }
+        Self {
+            pixels,
+            width: region.width,
+            height: region.height,
+            depth: self.depth,
+            format: self.format,
+            icc_profile: self.icc_profile.clone(),
+            alpha: AlphaCache::default(),
+        }
     }
 
-    /// The width of the region, in terminal cells
-    pub fn cell_width(&self) -> usize {
-        self.width
+    /// Crops the image to a [`Region`], returning the result as a new image.
+    ///
+    /// Unlike [`ImageView::with_region`], the pixels are copied, so the result
+    /// can be stored or modified independently of this image. The region is
+    /// clamped to the bounds of the image like [`ImageView::set_region`], and
+    /// an empty image (0x0) is returned if nothing of the image is selected.
+    pub fn crop(&self, region: Region) -> Image {
+        let region = region.clamped(self.width, self.height);
+        if region.width == 0 || region.height == 0 {
+            return self.copy_region(Region::default());
+        }
+        self.copy_region(region)
     }
 
-    /// The height of the region, in terminal cells, rounded up
-    pub fn cell_height(&self) -> usize {
-        self.height / 2 + self.height % 2
+    /// Center-crops the image to the largest region matching the aspect ratio
+    /// `w_ratio:h_ratio`, returning the result as a new image.
+    ///
+    /// The ratio is in terms of pixels. Since a terminal cell holds two
+    /// vertically stacked pixels, an area of `w` by `h` cells corresponds to a
+    /// ratio of `w:(h * 2)`. If either ratio component is zero, a copy of the
+    /// entire image is returned.
+    pub fn crop_to_aspect(&self, w_ratio: u32, h_ratio: u32) -> Image {
+        if w_ratio == 0 || h_ratio == 0 {
+            return self.clone();
+        }
+        let (w_ratio, h_ratio) = (w_ratio as u128, h_ratio as u128);
+        let (width, height) = (self.width as u128, self.height as u128);
+        let (new_width, new_height) = if width * h_ratio > height * w_ratio {
+            ((height * w_ratio / h_ratio) as usize, self.height)
+        } else {
+            (self.width, (width * h_ratio / w_ratio) as usize)
+        };
+        self.copy_region(Region {
+            x: (self.width - new_width) / 2,
+            y: (self.height - new_height) / 2,
+            width: new_width,
+            height: new_height,
+        })
     }
-}
 
-fn apply_alpha(val: u8, bg: u8, alpha: u8) -> u8 {
-    (((val as u16 * alpha as u16) + (bg as u16 * (255 - alpha) as u16)) / 255) as u8
-}
+    /// Splits the image into a grid of `cols` by `rows` equally sized tiles,
+    /// such as the sprites of a sprite sheet, returning them as new images in
+    /// row-major order.
+    ///
+    /// Each tile is `width / cols` by `height / rows` pixels. If the image size
+    /// is not evenly divisible, the remaining pixels at the right and bottom
+    /// edges are not part of any tile. If `cols` or `rows` is zero, no tiles are
+    /// returned.
+    pub fn split_grid(&self, cols: usize, rows: usize) -> Vec<Image> {
+        if cols == 0 || rows == 0 {
+            return Vec::new();
+        }
+        let (tile_width, tile_height) = (self.width / cols, self.height / rows);
+        let mut tiles = Vec::with_capacity(cols * rows);
+        for row in 0..rows {
+            for col in 0..cols {
+                tiles.push(self.copy_region(Region {
+                    x: col * tile_width,
+                    y: row * tile_height,
+                    width: tile_width,
+                    height: tile_height,
+                }));
+            }
+        }
+        tiles
+    }
 
-impl Pixel {
-    /// Converts a pixel to a [`Color`] value by blending with the provided background
-    /// color based on the alpha channel when needed.
-    pub fn on(&self, bg: BgColor) -> Color {
-        Color::Rgb(
-            apply_alpha(self.r, bg.r, self.a),
-            apply_alpha(self.g, bg.g, self.a),
-            apply_alpha(self.b, bg.b, self.a),
-        )
+    /// Finds the smallest region containing every pixel that is not fully
+    /// transparent, or [`None`] if the image has no such pixels.
+    ///
+    /// This can be used with [`ImageView::with_region`] to trim transparent
+    /// margins (e.g. of a padded sprite), so the content fills the render area.
+    /// See also [`opaque_bounds_above`](Image::opaque_bounds_above).
+    pub fn opaque_bounds(&self) -> Option<Region> {
+        self.opaque_bounds_above(0)
     }
-}
 
-impl<'a> Iterator for ViewPixels<'a> {
-    type Item = &'a Pixel;
+    /// Finds the smallest region containing every pixel with an alpha value
+    /// above `threshold`, or [`None`] if the image has no such pixels.
+    pub fn opaque_bounds_above(&self, threshold: u8) -> Option<Region> {
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for (y, row) in self.pixels.chunks(self.width.max(1)).enumerate() {
+            let Some(left) = row.iter().position(|pix| pix.a > threshold) else {
+                continue;
+            };
+            let right = row.iter().rposition(|pix| pix.a > threshold).unwrap_or(left);
+            bounds = Some(match bounds {
+                None => (left, y, right, y),
+                Some((l, t, r, _)) => (l.min(left), t, r.max(right), y),
+            });
+        }
+        bounds.map(|(left, top, right, bottom)| Region {
+            x: left,
+            y: top,
+            width: right - left + 1,
+            height: bottom - top + 1,
+        })
+    }
+
+    /// Checks whether any pixel of the image is not fully opaque (i.e. has an
+    /// alpha value below 255).
+    ///
+    /// Fully opaque images render the same regardless of the background color
+    /// or checkerboard, so this can be used e.g. to decide whether to offer a
+    /// transparency preview.
+    ///
+    /// The result is computed on the first call and cached. The cache is
+    /// discarded by every method that may change the alpha channel, including
+    /// [`pixels_mut`](Image::pixels_mut) and [`pixel_mut`](Image::pixel_mut)
+    /// (even if the pixels are not actually modified), so the next call scans
+    /// the pixels again.
+    pub fn has_alpha(&self) -> bool {
+        self.alpha.get(&self.pixels)
+    }
+
+    /// Checks whether at least a `threshold` fraction (from `0.0` to `1.0`) of
+    /// the pixels are fully transparent, such as for flagging placeholder or
+    /// partially decoded images. An empty image is considered fully
+    /// transparent.
+    pub fn is_mostly_transparent(&self, threshold: f32) -> bool {
+        if self.pixels.is_empty() {
+            return true;
+        }
+        let transparent = self.pixels.iter().filter(|pix| pix.a == 0).count();
+        transparent as f32 >= threshold * self.pixels.len() as f32
+    }
+
+    /// Checks whether every pixel of the image is the same, such as for
+    /// flagging images that decoded to a solid color. An empty image is
+    /// considered blank.
+    pub fn is_blank(&self) -> bool {
+        match self.pixels.split_first() {
+            Some((first, rest)) => rest.iter().all(|pix| pix == first),
+            None => true,
+        }
+    }
+
+    /// Checks whether the image looks the same as `other`, unlike
+    /// [`PartialEq`], which compares every channel strictly, including the
+    /// colors of fully transparent pixels.
+    ///
+    /// The images must have the same dimensions, and each pair of pixels must
+    /// either both be fully transparent, in which case their colors are
+    /// ignored, or have the same alpha and [`Pixel::eq_rgb`] colors.
+    pub fn eq_visible(&self, other: &Image) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self
+                .pixels
+                .iter()
+                .zip(other.pixels.iter())
+                .all(|(a, b)| (a.a == 0 && b.a == 0) || (a.a == b.a && a.eq_rgb(b)))
+    }
+
+    /// Computes the average color of the image, weighting each pixel by its
+    /// alpha value, so fully transparent pixels do not affect the color.
+    ///
+    /// The alpha channel of the result is the average alpha value of all
+    /// pixels. [`None`] is returned for an empty image, and a fully transparent
+    /// image yields fully transparent black.
+    pub fn average_color(&self) -> Option<Pixel> {
+        if self.pixels.is_empty() {
+            return None;
+        }
+        let mut sum = [0u64; 4];
+        for pix in self.pixels.iter() {
+            let a = pix.a as u64;
+            sum[0] += pix.r as u64 * a;
+            sum[1] += pix.g as u64 * a;
+            sum[2] += pix.b as u64 * a;
+            sum[3] += a;
+        }
+        let count = self.pixels.len() as u64;
+        let weight = sum[3].max(1);
+        let avg = |sum: u64| ((sum + weight / 2) / weight) as u8;
+        Some(Pixel {
+            r: avg(sum[0]),
+            g: avg(sum[1]),
+            b: avg(sum[2]),
+            a: ((sum[3] + count / 2) / count) as u8,
+        })
+    }
+
+    /// Computes a fast 64-bit fingerprint of the image content.
+    ///
+    /// The fingerprint covers the dimensions and every channel of every
+    /// pixel, using the FNV-1a hash. Unlike the [`Hash`] implementation, the
+    /// result does not depend on a [`Hasher`] and is stable across runs and
+    /// platforms, so it can be used as a cache key or to detect that an image
+    /// has changed.
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint(&self.pixels, self.width, self.height)
+    }
+}
+
+impl Region {
+    /// Clamps the region to the bounds of a `width` by `height` image. A region
+    /// starting out of bounds becomes empty.
+    fn clamped(self, width: usize, height: usize) -> Region {
+        let Region {
+            mut x,
+            mut y,
+            width: mut region_width,
+            height: mut region_height,
+        } = self;
+        if x > width || y > height {
+            x = 0;
+            y = 0;
+            region_width = 0;
+            region_height = 0;
+        } else {
+            if x + region_width > width {
+                region_width = width - x;
+            }
+            if y + region_height > height {
+                region_height = height - y;
+            }
+        }
+        Region {
+            x,
+            y,
+            width: region_width,
+            height: region_height,
+        }
+    }
+
+    /// Creates a region from coordinates and size in terms of terminal cells.
+    ///
+    /// Each cell is one pixel wide and two pixels tall, so the vertical
+    /// coordinate and height are doubled, matching how regions are rendered.
+    pub const fn from_cells(cell_x: usize, cell_y: usize, cell_w: usize, cell_h: usize) -> Region {
+        Region {
+            x: cell_x,
+            y: cell_y * 2,
+            width: cell_w,
+            height: cell_h * 2,
+        }
+    }
+
+    /// The X-coordinate (horizontal) of the top-left pixel in terms of terminal cells
+    pub const fn cell_x(&self) -> usize {
+        self.x
+    }
+
+    /// The Y-coordinate (vertical) of the top-left pixel in terms of terminal cells, rounded up
+    pub const fn cell_y(&self) -> usize {
+        self.y / 2 + self.y % 2
+    }
+
+    /// The width of the region, in terminal cells
+    pub const fn cell_width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the region, in terminal cells, rounded up. With an odd
+    /// height, the last cell is only half filled. See also
+    /// [`cell_height_floor`](Region::cell_height_floor).
+    pub const fn cell_height(&self) -> usize {
+        self.height / 2 + self.height % 2
+    }
+
+    /// The height of the region, in terminal cells, rounded down, i.e. the
+    /// number of cells completely filled by the region. This is useful for
+    /// packing images vertically without a gap after a half filled cell.
+    pub const fn cell_height_floor(&self) -> usize {
+        self.height / 2
+    }
+}
+
+impl Pixel {
+    /// Blends a single color channel `val` with the background channel `bg`,
+    /// according to `alpha`.
+    ///
+    /// An `alpha` of 0 yields `bg`, and an `alpha` of 255 yields `val`. This is
+    /// the same blending used by [`Pixel::on`] when rendering, so it can be used
+    /// to composite cells consistently with this crate.
+    ///
+    /// Note that blending is performed directly on the (gamma-encoded) sRGB
+    /// values rather than in linear light. This is cheap and matches how most
+    /// terminals and simple compositors behave, but partially transparent
+    /// pixels may appear slightly darker than a physically correct blend.
+    pub fn blend_channel(val: u8, bg: u8, alpha: u8) -> u8 {
+        (((val as u16 * alpha as u16) + (bg as u16 * (255 - alpha) as u16)) / 255) as u8
+    }
+
+    /// Converts a pixel to a [`Color`] value by blending with the provided background
+    /// color based on the alpha channel when needed.
+    ///
+    /// Fully opaque pixels are converted directly, without any blending math.
+    pub fn on(&self, bg: BgColor) -> Color {
+        if self.a == 255 {
+            return Color::Rgb(self.r, self.g, self.b);
+        }
+        Color::Rgb(
+            Self::blend_channel(self.r, bg.r, self.a),
+            Self::blend_channel(self.g, bg.g, self.a),
+            Self::blend_channel(self.b, bg.b, self.a),
+        )
+    }
+
+    fn faded(&self, opacity: f32) -> Pixel {
+        Pixel {
+            a: (self.a as f32 * opacity.clamp(0.0, 1.0)).round() as u8,
+            ..*self
+        }
+    }
+
+    fn over(&self, bg: BgColor) -> Pixel {
+        if self.a == 255 {
+            return *self;
+        }
+        Pixel {
+            r: Self::blend_channel(self.r, bg.r, self.a),
+            g: Self::blend_channel(self.g, bg.g, self.a),
+            b: Self::blend_channel(self.b, bg.b, self.a),
+            a: 255,
+        }
+    }
+
+    /// The perceived brightness of the pixel, from 0 (black) to 255 (white).
+    ///
+    /// Luminance is computed from the RGB channels with the Rec. 601 weights
+    /// (`0.299 R + 0.587 G + 0.114 B`). The alpha channel is ignored. See
+    /// [`luminance_with`](Pixel::luminance_with) for other weights.
+    pub fn luminance(&self) -> u8 {
+        self.luminance_with(LumaWeights::Rec601)
+    }
+
+    /// The brightness of the pixel, from 0 (black) to 255 (white), computed
+    /// from the RGB channels with the given [`LumaWeights`]. The alpha channel
+    /// is ignored.
+    pub fn luminance_with(&self, weights: LumaWeights) -> u8 {
+        let (r, g, b) = weights.coefficients();
+        (r * self.r as f32 + g * self.g as f32 + b * self.b as f32).round() as u8
+    }
+
+    /// Checks whether the RGB channels of the pixel equal those of `other`,
+    /// ignoring the alpha channel
+    pub fn eq_rgb(&self, other: &Pixel) -> bool {
+        self.r == other.r && self.g == other.g && self.b == other.b
+    }
+
+    /// Linearly interpolates between this pixel and `other`, channel by channel
+    /// (including alpha).
+    ///
+    /// A factor `t` of `0.0` yields this pixel, and `1.0` yields `other`. The
+    /// factor is clamped to that range.
+    pub fn blend(&self, other: Pixel, t: f32) -> Pixel {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Pixel {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+            a: mix(self.a, other.a),
+        }
+    }
+}
+
+impl<'a> Iterator for ViewPixels<'a> {
+    type Item = &'a Pixel;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.x == usize::MAX {
+        if self.x == usize::MAX || self.region.width == 0 || self.region.height == 0 {
             return None;
         }
 
@@ -509,13 +1729,219 @@ impl<'a> Iterator for ViewPixels<'a> {
     }
 }
 
+impl From<image::DynamicImage> for Image {
+    /// Converts a decoded image, like [`load`](Image::load) does after
+    /// decoding, so images decoded with custom options of the [`image`] crate
+    /// can be rendered. 8-bit, 16-bit, and floating point images are
+    /// supported, and other pixel types are converted through 8-bit RGBA.
+    /// Floating point images are converted with [`ToneMap::Clamp`].
+    fn from(im: image::DynamicImage) -> Self {
+        Self::from_image(&im, ToneMap::Clamp)
+    }
+}
+
+impl From<&image::DynamicImage> for Image {
+    /// Converts a decoded image like the conversion from an owned
+    /// [`DynamicImage`](image::DynamicImage)
+    fn from(im: &image::DynamicImage) -> Self {
+        Self::from_image(im, ToneMap::Clamp)
+    }
+}
+
+impl TryFrom<&[u8]> for Image {
+    type Error = Error;
+
+    /// Decodes an image from encoded bytes (e.g. the content of a PNG file)
+    /// like [`load`](Image::load), detecting the format from the content
+    fn try_from(data: &[u8]) -> Result<Self> {
+        Self::load(std::io::Cursor::new(data))
+    }
+}
+
+/// Converts an area of terminal cells to the region of pixels it covers. See
+/// [`Region::from_cells`].
+///
+/// The resulting vertical coordinate and height are always even, so the cell
+/// accessors ([`Region::cell_x`], [`Region::cell_y`], [`Region::cell_width`],
+/// and [`Region::cell_height`]) return the coordinates and size of the
+/// original area without any rounding.
 impl From<Rect> for Region {
     fn from(area: Rect) -> Self {
+        Self::from_cells(
+            area.x as usize,
+            area.y as usize,
+            area.width as usize,
+            area.height as usize,
+        )
+    }
+}
+
+impl Default for Checkerboard {
+    fn default() -> Self {
+        Self {
+            size: 2,
+            c1: BgColor {
+                r: 0x99,
+                g: 0x99,
+                b: 0x99,
+            },
+            c2: BgColor {
+                r: 0x66,
+                g: 0x66,
+                b: 0x66,
+            },
+        }
+    }
+}
+
+impl Checkerboard {
+    /// Gets the background color at the given pixel coordinates
+    pub fn color_at(&self, x: usize, y: usize) -> BgColor {
+        let size = self.size.max(1);
+        if ((x / size) + (y / size)) % 2 == 0 {
+            self.c1
+        } else {
+            self.c2
+        }
+    }
+}
+
+impl LumaWeights {
+    /// The weights of the red, green, and blue channels, which sum to 1
+    fn coefficients(self) -> (f32, f32, f32) {
+        match self {
+            LumaWeights::Rec601 => (0.299, 0.587, 0.114),
+            LumaWeights::Rec709 => (0.2126, 0.7152, 0.0722),
+            LumaWeights::Average => (1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0),
+        }
+    }
+}
+
+impl SampleMode {
+    /// Maps the pixel index `idx` along an axis of `len` pixels into the axis,
+    /// or [`None`] if it is not drawn
+    fn index(self, idx: isize, len: usize) -> Option<usize> {
+        let len = len as isize;
+        match self {
+            _ if (0..len).contains(&idx) => Some(idx as usize),
+            SampleMode::None => None,
+            SampleMode::Clamp => Some(idx.clamp(0, len - 1) as usize),
+            SampleMode::Wrap => Some(idx.rem_euclid(len) as usize),
+        }
+    }
+
+    /// Maps the fractional position `pos` along an axis of `len` pixels into
+    /// the axis, or [`None`] if it is not drawn
+    fn position(self, pos: f32, len: usize) -> Option<f32> {
+        let len = len as f32;
+        match self {
+            _ if (0.0..len).contains(&pos) => Some(pos),
+            SampleMode::None => None,
+            // The center of the edge pixel
+            SampleMode::Clamp => Some(if pos < 0.0 { 0.5 } else { len - 0.5 }),
+            SampleMode::Wrap => Some(pos.rem_euclid(len)),
+        }
+    }
+}
+
+impl ClipShape {
+    /// Checks whether the center of the pixel at `x`, `y` is inside of the
+    /// shape fit to the given bounds (`left`, `top`, `right`, `bottom`).
+    fn contains(&self, x: usize, y: usize, bounds: (f32, f32, f32, f32)) -> bool {
+        let (left, top, right, bottom) = bounds;
+        let x = x as f32 + 0.5;
+        let y = y as f32 + 0.5;
+        match *self {
+            ClipShape::Rectangle => true,
+            ClipShape::Circle => {
+                let rx = (right - left) / 2.0;
+                let ry = (bottom - top) / 2.0;
+                if rx <= 0.0 || ry <= 0.0 {
+                    return false;
+                }
+                let dx = (x - (left + rx)) / rx;
+                let dy = (y - (top + ry)) / ry;
+                dx * dx + dy * dy <= 1.0
+            }
+            ClipShape::RoundedRect { radius } => {
+                let radius = (radius as f32)
+                    .min((right - left) / 2.0)
+                    .min((bottom - top) / 2.0);
+                let dx = x - x.clamp(left + radius, right - radius);
+                let dy = y - y.clamp(top + radius, bottom - radius);
+                dx * dx + dy * dy <= radius * radius
+            }
+        }
+    }
+}
+
+impl Easing {
+    /// Maps linear progress `t` in the range `[0.0, 1.0]` to eased progress
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+impl RegionTween {
+    /// Creates a tween from the region `from` to the region `to` of `image`,
+    /// lasting `duration`.
+    ///
+    /// Both regions are clamped to the bounds of `image`, so every intermediate
+    /// region is within bounds as well. The tween defaults to [`Easing::Linear`].
+    pub fn new(image: &Image, from: Region, to: Region, duration: std::time::Duration) -> Self {
         Self {
-            x: area.x as usize,
-            y: area.y as usize * 2,
-            width: area.width as usize,
-            height: area.height as usize * 2,
+            from: from.clamped(image.width, image.height),
+            to: to.clamped(image.width, image.height),
+            duration,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Factory pattern setter for the [`Easing`] function of the tween
+    #[must_use = "method returns the modified value"]
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Gets the [`Easing`] function of the tween
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Gets the total duration of the tween
+    pub fn duration(&self) -> std::time::Duration {
+        self.duration
+    }
+
+    /// Checks whether the tween has reached its final region after `elapsed` time
+    pub fn is_finished(&self, elapsed: std::time::Duration) -> bool {
+        elapsed >= self.duration
+    }
+
+    /// Gets the intermediate region after `elapsed` time since the start of the
+    /// tween. Times past the end of the tween yield the final region.
+    pub fn region_at(&self, elapsed: std::time::Duration) -> Region {
+        if self.is_finished(elapsed) {
+            return self.to;
+        }
+        let t = self
+            .easing
+            .apply(elapsed.as_secs_f32() / self.duration.as_secs_f32());
+        let lerp = |a: usize, b: usize| (a as f32 + (b as f32 - a as f32) * t).round() as usize;
+        let x = lerp(self.from.x, self.to.x);
+        let y = lerp(self.from.y, self.to.y);
+        let right = lerp(self.from.x + self.from.width, self.to.x + self.to.width);
+        let bottom = lerp(self.from.y + self.from.height, self.to.y + self.to.height);
+        Region {
+            x,
+            y,
+            width: right.saturating_sub(x),
+            height: bottom.saturating_sub(y),
         }
     }
 }
@@ -532,10 +1958,36 @@ impl<'a> ImageView<'a> {
     /// The returned [`ImageView`] defaults to [`Fit::Zoom`] and black background
     /// color (`#000000`).
     pub fn new(image: &'a Image) -> Self {
-        let width = image.width;
-        let height = image.height;
         Self {
-            image,
+            image: Some(image),
+            ..Self::raw(&image.pixels, image.width, image.height)
+        }
+    }
+
+    /// Returns an [`ImageView`] of pixels borrowed from a buffer owned
+    /// elsewhere, without constructing an [`Image`].
+    ///
+    /// The pixels are ordered row by row, like [`Image::pixels`]. No copy of
+    /// the pixels is made, so this can be used to render from memory that is
+    /// already managed by the caller (e.g. an arena or a memory mapped file).
+    /// The defaults are the same as for [`ImageView::new`].
+    ///
+    /// An [`Error::InvalidInput`] is returned if the length of `pixels` is not
+    /// `width * height`.
+    pub fn from_raw(pixels: &'a [Pixel], width: usize, height: usize) -> Result<Self> {
+        if width.checked_mul(height) != Some(pixels.len()) {
+            return Err(dimension_mismatch());
+        }
+        Ok(Self::raw(pixels, width, height))
+    }
+
+    /// Creates a view of raw pixels, which must be `width * height` long
+    fn raw(pixels: &'a [Pixel], width: usize, height: usize) -> Self {
+        Self {
+            image: None,
+            pixels,
+            width,
+            height,
             fit: Fit::Zoom,
             region: Region {
                 x: 0,
@@ -544,174 +1996,2158 @@ impl<'a> ImageView<'a> {
                 height,
             },
             bg: BgColor::default(),
+            checkerboard: None,
+            clip_shape: ClipShape::Rectangle,
+            smooth_edges: false,
+            clear: true,
+            transparency_passthrough: false,
+            half_cell_offset: false,
+            flip_horizontal: false,
+            flip_vertical: false,
+            rotation: Rotation::None,
+            mode: RenderMode::HalfBlock,
+            resample: Resample::Nearest,
+            auto_resample: None,
+            subpixel_offset: (0, 0),
+            sample_mode: SampleMode::None,
+            cvd: Cvd::None,
+            luma_weights: LumaWeights::Rec601,
+            ascii_ramp: mode::ASCII_RAMP,
+            color_depth: ColorDepth::TrueColor,
+            dither: false,
+            protocol: Protocol::HalfBlock,
+            font_size: (8, 16),
+            border: None,
+            pixel_hook: None,
         }
     }
 
     /// Factory pattern setter for the [`Fit`] mode of the view
+    #[must_use = "method returns the modified value"]
     pub fn with_fit(mut self, fit: Fit) -> Self {
         self.set_fit(fit);
         self
     }
 
     /// Factory pattern setter for the [`Region`] of the view
+    #[must_use = "method returns the modified value"]
     pub fn with_region(mut self, region: Region) -> Self {
         self.set_region(region);
         self
     }
 
+    /// Factory pattern setter for the [`Region`] of the view, in terms of
+    /// terminal cells (see [`Region::from_cells`]). This is convenient when
+    /// selecting a region with the mouse, where positions are in cells.
+    #[must_use = "method returns the modified value"]
+    pub fn with_cell_region(
+        mut self,
+        cell_x: usize,
+        cell_y: usize,
+        cell_w: usize,
+        cell_h: usize,
+    ) -> Self {
+        self.set_region(Region::from_cells(cell_x, cell_y, cell_w, cell_h));
+        self
+    }
+
     /// Factory pattern setter for the background color of the view
+    #[must_use = "method returns the modified value"]
     pub fn with_bg_color(mut self, color: BgColor) -> Self {
         self.set_bg_color(color);
         self
     }
 
-    /// Setter for the [`Fit`] mode of the view
-    pub fn set_fit(&mut self, fit: Fit) {
-        self.fit = fit;
+    /// Factory pattern setter for a [`Checkerboard`] background of the view.
+    ///
+    /// The checkerboard takes precedence over the background color.
+    #[must_use = "method returns the modified value"]
+    pub fn with_checkerboard_bg(mut self, checkerboard: Checkerboard) -> Self {
+        self.set_checkerboard_bg(Some(checkerboard));
+        self
     }
 
-    /// Setter for the [`Region`] of the view
-    pub fn set_region(&mut self, region: Region) {
-        let Region {
-            mut x,
-            mut y,
-            mut width,
-            mut height,
-        } = region;
-        if x > self.image.width || y > self.image.height {
-            x = 0;
-            y = 0;
-            width = 0;
-            height = 0;
-        } else {
-            if x + width > self.image.width {
-                width = self.image.width - x;
-            }
-            if y + height > self.image.height {
-                height = self.image.height - y;
-            }
-        }
-        self.region = Region {
-            x,
-            y,
-            width,
-            height,
-        };
+    /// Factory pattern setter for the [`ClipShape`] of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_clip_shape(mut self, shape: ClipShape) -> Self {
+        self.set_clip_shape(shape);
+        self
     }
 
-    /// Setter for the background color of the view
-    pub fn set_bg_color(&mut self, color: BgColor) {
-        self.bg = color;
+    /// Factory pattern setter for smoothing the edges of the scaled image.
+    ///
+    /// See [`set_smooth_edges`](ImageView::set_smooth_edges).
+    #[must_use = "method returns the modified value"]
+    pub fn with_smooth_edges(mut self, smooth: bool) -> Self {
+        self.set_smooth_edges(smooth);
+        self
     }
 
-    /// Gets the original image
-    pub fn image(&self) -> &'a Image {
-        self.image
+    /// Factory pattern setter for whether the view clears letterbox cells.
+    ///
+    /// See [`set_clear`](ImageView::set_clear).
+    #[must_use = "method returns the modified value"]
+    pub fn with_clear(mut self, clear: bool) -> Self {
+        self.set_clear(clear);
+        self
     }
 
-    /// Gets the current [`Fit`] mode of the view
-    pub fn fit(&self) -> Fit {
-        self.fit
+    /// Factory pattern setter for letting the terminal background show through
+    /// fully transparent pixels.
+    ///
+    /// See [`set_transparency_passthrough`](ImageView::set_transparency_passthrough).
+    #[must_use = "method returns the modified value"]
+    pub fn with_transparency_passthrough(mut self, passthrough: bool) -> Self {
+        self.set_transparency_passthrough(passthrough);
+        self
     }
 
-    /// Gets the current [`Region`] of the view
-    pub fn region(&self) -> &Region {
-        &self.region
+    /// Factory pattern setter for shifting the image down by half a cell.
+    ///
+    /// See [`set_half_cell_offset`](ImageView::set_half_cell_offset).
+    #[must_use = "method returns the modified value"]
+    pub fn with_half_cell_offset(mut self, offset: bool) -> Self {
+        self.set_half_cell_offset(offset);
+        self
     }
 
-    /// Returns an iterator over the pixels of the view according to its [`Region`]
-    pub fn pixels(&self) -> ViewPixels<'a> {
-        ViewPixels {
-            pixels: self.image.pixels(),
-            region: self.region,
-            real_width: self.image.width,
-            x: self.region.x,
-            y: self.region.y,
-        }
+    /// Factory pattern setter for mirroring the view horizontally
+    #[must_use = "method returns the modified value"]
+    pub fn with_flip_horizontal(mut self, flip: bool) -> Self {
+        self.set_flip_horizontal(flip);
+        self
     }
 
-    /// Gets the pixel value at given pixel coordinates. [`None`](std::option::Option)
-    /// is returned if the coordinates are out of bounds.
-    pub fn pixel(&self, x: usize, y: usize) -> Option<Pixel> {
-        if x >= self.region.width || y >= self.region.height {
-            None
-        } else {
-            self.image
-                .pixel(x + self.region.x, y + self.region.y)
-                .copied()
-        }
+    /// Factory pattern setter for mirroring the view vertically
+    #[must_use = "method returns the modified value"]
+    pub fn with_flip_vertical(mut self, flip: bool) -> Self {
+        self.set_flip_vertical(flip);
+        self
     }
-}
 
-const PIXEL_CHAR: char = '▀';
+    /// Factory pattern setter for the [`Rotation`] of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_rotation(mut self, rotation: Rotation) -> Self {
+        self.set_rotation(rotation);
+        self
+    }
 
-impl<'a> Widget for ImageView<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.width as usize == self.region.width
-            && area.height as usize * 2 == self.region.height
-        {
-            for x in 0..area.width {
-                for y in 0..area.height {
-                    let pix_x = x as usize;
-                    let pix_y1 = y as usize * 2;
-                    let pix_y2 = pix_y1 + 1;
-                    let pix1 = self.pixel(pix_x, pix_y1).unwrap_or_default().on(self.bg);
-                    let pix2 = self.pixel(pix_x, pix_y2).unwrap_or_default().on(self.bg);
-                    buf.get_mut(x, y)
-                        .set_char(PIXEL_CHAR)
-                        .set_fg(pix1)
-                        .set_bg(pix2);
-                }
-            }
-        } else {
-            let mut zoom_x = area.width as f32 / self.region.width as f32;
-            let mut zoom_y = area.height as f32 * 2.0 / self.region.height as f32;
-            let mut x_pos = 0u16;
-            let mut y_pos = 0u16;
-            if let Fit::Zoom = self.fit {
-                if zoom_x < zoom_y {
-                    y_pos = (((area.height as usize * 2)
-                        - (self.region.height as f32 * zoom_x) as usize)
-                        / 4) as u16;
-                    zoom_y = zoom_x;
-                } else {
-                    x_pos = ((area.width as usize - (self.region.width as f32 * zoom_y) as usize)
-                        / 2) as u16;
-                    zoom_x = zoom_y;
-                }
-            }
+    /// Factory pattern setter for the [`RenderMode`] of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_mode(mut self, mode: RenderMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
 
-            for x in 0..area.width {
-                for y in 0..area.height {
-                    if x < x_pos || y < y_pos {
-                        buf.get_mut(x, y).set_char(' ').set_bg(Color::Reset);
-                        continue;
-                    }
-                    let pix_x = ((x - x_pos) as f32 / zoom_x) as usize;
-                    let y1 = (y - y_pos) as usize * 2;
-                    let y2 = y1 + 1;
-                    let pix_y1 = (y1 as f32 / zoom_y) as usize;
-                    let pix_y2 = (y2 as f32 / zoom_y) as usize;
-                    let pix1 = self.pixel(pix_x, pix_y1);
-                    let pix2 = self.pixel(pix_x, pix_y2);
-                    if pix1.is_none() && pix2.is_none() {
-                        buf.get_mut(x, y).set_char(' ').set_bg(Color::Reset);
-                        continue;
-                    }
-                    let pix1 = match pix1 {
-                        None => Color::Reset,
-                        Some(pix) => pix.on(self.bg),
-                    };
-                    let pix2 = match pix2 {
-                        None => Color::Reset,
-                        Some(pix) => pix.on(self.bg),
-                    };
-                    buf.get_mut(x, y)
-                        .set_char(PIXEL_CHAR)
-                        .set_fg(pix1)
-                        .set_bg(pix2);
+    /// Factory pattern setter for the [`Resample`] filter of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_resample(mut self, resample: Resample) -> Self {
+        self.set_resample(resample);
+        self
+    }
+
+    /// Factory pattern setter for automatically choosing the [`Resample`]
+    /// filter, switching to [`Resample::Average`] when downscaling by more than
+    /// `crossover`.
+    ///
+    /// See [`set_auto_resample`](ImageView::set_auto_resample).
+    #[must_use = "method returns the modified value"]
+    pub fn with_auto_resample(mut self, crossover: f32) -> Self {
+        self.set_auto_resample(Some(crossover));
+        self
+    }
+
+    /// Factory pattern setter for the fractional part of the origin of the
+    /// [`Region`] of the view.
+    ///
+    /// See [`set_subpixel_offset`](ImageView::set_subpixel_offset).
+    #[must_use = "method returns the modified value"]
+    pub fn with_subpixel_offset(mut self, x: f32, y: f32) -> Self {
+        self.set_subpixel_offset(x, y);
+        self
+    }
+
+    /// Factory pattern setter for the [`SampleMode`] of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_sample_mode(mut self, mode: SampleMode) -> Self {
+        self.set_sample_mode(mode);
+        self
+    }
+
+    /// Factory pattern setter for the [`Cvd`] (color blindness) simulated by
+    /// the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_cvd(mut self, cvd: Cvd) -> Self {
+        self.set_cvd(cvd);
+        self
+    }
+
+    /// Factory pattern setter for the [`LumaWeights`] used by render modes
+    /// based on brightness
+    #[must_use = "method returns the modified value"]
+    pub fn with_luma_weights(mut self, weights: LumaWeights) -> Self {
+        self.set_luma_weights(weights);
+        self
+    }
+
+    /// Factory pattern setter for the characters used by
+    /// [`RenderMode::Ascii`] and [`RenderMode::Monochrome`], from darkest to
+    /// brightest
+    #[must_use = "method returns the modified value"]
+    pub fn with_ascii_ramp(mut self, ramp: &'a str) -> Self {
+        self.set_ascii_ramp(ramp);
+        self
+    }
+
+    /// Factory pattern setter for the [`ColorDepth`] the rendered colors are
+    /// reduced to
+    #[must_use = "method returns the modified value"]
+    pub fn with_color_depth(mut self, depth: ColorDepth) -> Self {
+        self.set_color_depth(depth);
+        self
+    }
+
+    /// Factory pattern setter for whether colors are dithered when reduced
+    /// to the [`ColorDepth`] of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_dither(mut self, dither: bool) -> Self {
+        self.set_dither(dither);
+        self
+    }
+
+    /// Factory pattern setter for the [`Protocol`] of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_protocol(mut self, protocol: Protocol) -> Self {
+        self.set_protocol(protocol);
+        self
+    }
+
+    /// Factory pattern setter for the size of a terminal cell in pixels, for
+    /// graphics protocols
+    #[must_use = "method returns the modified value"]
+    pub fn with_font_size(mut self, width: u16, height: u16) -> Self {
+        self.set_font_size(width, height);
+        self
+    }
+
+    /// Factory pattern setter for a border of the given color around the
+    /// rendered image.
+    ///
+    /// See [`set_border`](ImageView::set_border).
+    #[must_use = "method returns the modified value"]
+    pub fn with_border(mut self, color: Color) -> Self {
+        self.set_border(Some(color));
+        self
+    }
+
+    /// Factory pattern setter for a callback applied to each pixel as it is
+    /// sampled.
+    ///
+    /// See [`set_pixel_hook`](ImageView::set_pixel_hook).
+    #[must_use = "method returns the modified value"]
+    pub fn with_pixel_hook(mut self, hook: &'a PixelHookFn) -> Self {
+        self.set_pixel_hook(Some(hook));
+        self
+    }
+
+    /// Setter for the [`Fit`] mode of the view
+    pub fn set_fit(&mut self, fit: Fit) {
+        self.fit = fit;
+    }
+
+    /// Setter for the [`Region`] of the view
+    pub fn set_region(&mut self, region: Region) {
+        self.region = region.clamped(self.width, self.height);
+    }
+
+    /// Setter for the background color of the view
+    pub fn set_bg_color(&mut self, color: BgColor) {
+        self.bg = color;
+    }
+
+    /// Setter for the [`Checkerboard`] background of the view. When [`None`],
+    /// the background color is used instead.
+    pub fn set_checkerboard_bg(&mut self, checkerboard: Option<Checkerboard>) {
+        self.checkerboard = checkerboard;
+    }
+
+    /// Setter for the [`ClipShape`] of the view
+    pub fn set_clip_shape(&mut self, shape: ClipShape) {
+        self.clip_shape = shape;
+    }
+
+    /// Setter for smoothing the edges of the scaled image. Disabled by default.
+    ///
+    /// When the image is scaled by a non-integer factor (typically with
+    /// [`Fit::Zoom`]), the edge between the image and the letterbox usually
+    /// falls in the middle of a pixel. When enabled, such partially covered edge
+    /// pixels are made proportionally transparent, so they blend toward the
+    /// background instead of forming a hard, jagged seam. This costs some extra
+    /// computation per rendered pixel.
+    pub fn set_smooth_edges(&mut self, smooth: bool) {
+        self.smooth_edges = smooth;
+    }
+
+    /// Gets whether the edges of the scaled image are smoothed
+    pub fn smooth_edges(&self) -> bool {
+        self.smooth_edges
+    }
+
+    /// Setter for whether the view clears letterbox cells. Enabled by default.
+    ///
+    /// When enabled, cells of the render area not covered by the image are
+    /// overwritten with a blank [`Color::Reset`] cell. When disabled, those
+    /// cells are left untouched, so the image can be layered over content
+    /// previously rendered into the [`Buffer`] by other widgets. Cells only
+    /// half covered by the image keep the background color of the existing
+    /// cell for their uncovered half.
+    ///
+    /// Cells outside of the [`ClipShape`], cells with only fully transparent
+    /// pixels when [transparency passthrough](ImageView::set_transparency_passthrough)
+    /// is enabled, and positions beyond the region with [`SampleMode::None`]
+    /// count as letterbox. This applies to every way of rendering the view,
+    /// including [`cells`](ImageView::cells) (which skips them), [`ImageRow`],
+    /// and [`Crossfade`] (which clears if either of its views does).
+    pub fn set_clear(&mut self, clear: bool) {
+        self.clear = clear;
+    }
+
+    /// Gets whether the view clears letterbox cells
+    pub fn clear(&self) -> bool {
+        self.clear
+    }
+
+    /// Setter for letting the terminal background show through fully
+    /// transparent pixels. Disabled by default.
+    ///
+    /// By default, every pixel is blended with the background color, so fully
+    /// transparent pixels are drawn in that color. When enabled, pixels with an
+    /// alpha value of 0 are drawn like letterbox instead: as [`Color::Reset`],
+    /// or, with clearing disabled (see [`set_clear`](ImageView::set_clear)), as
+    /// the existing background of the cell. Where only one pixel of a cell is
+    /// transparent, the other is drawn with an upper (`▀`) or lower (`▄`) half
+    /// block, and a cell with both pixels transparent is left blank. Partially
+    /// transparent pixels are still blended with the background color.
+    pub fn set_transparency_passthrough(&mut self, passthrough: bool) {
+        self.transparency_passthrough = passthrough;
+    }
+
+    /// Gets whether the terminal background shows through fully transparent
+    /// pixels
+    pub fn transparency_passthrough(&self) -> bool {
+        self.transparency_passthrough
+    }
+
+    /// Setter for shifting the image down by half a cell (one pixel row) within
+    /// the render area. Disabled by default.
+    ///
+    /// Each cell holds two vertically stacked pixels, so without an offset the
+    /// image can only be positioned in steps of two pixels. With the offset,
+    /// the first pixel row of the image is drawn in the bottom half of the
+    /// first cell row (using a lower half block, `▄`), allowing an image to be
+    /// moved vertically on screen one pixel at a time. The offset also allows a
+    /// region with an odd height to take the exact-size path (see
+    /// [`is_exact`](ImageView::is_exact)).
+    pub fn set_half_cell_offset(&mut self, offset: bool) {
+        self.half_cell_offset = offset;
+    }
+
+    /// Gets whether the image is shifted down by half a cell
+    pub fn half_cell_offset(&self) -> bool {
+        self.half_cell_offset
+    }
+
+    /// Setter for mirroring the view horizontally.
+    ///
+    /// Like [`set_rotation`](ImageView::set_rotation), this only affects how
+    /// pixels are sampled when rendering, so the [`Image`] is not copied or
+    /// modified. Flips are applied after rotation.
+    pub fn set_flip_horizontal(&mut self, flip: bool) {
+        self.flip_horizontal = flip;
+    }
+
+    /// Setter for mirroring the view vertically. See
+    /// [`set_flip_horizontal`](ImageView::set_flip_horizontal).
+    pub fn set_flip_vertical(&mut self, flip: bool) {
+        self.flip_vertical = flip;
+    }
+
+    /// Setter for the [`Rotation`] of the view.
+    ///
+    /// The rotation only affects how pixels are sampled when rendering, so the
+    /// [`Image`] is not copied or modified. The [`Region`] is still specified in
+    /// the coordinates of the original image, but for [`Rotation::Clockwise90`]
+    /// and [`Rotation::Clockwise270`], the rendered width and height of the
+    /// region are swapped.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.rotation = rotation;
+    }
+
+    /// Gets whether the view is mirrored horizontally
+    pub fn flip_horizontal(&self) -> bool {
+        self.flip_horizontal
+    }
+
+    /// Gets whether the view is mirrored vertically
+    pub fn flip_vertical(&self) -> bool {
+        self.flip_vertical
+    }
+
+    /// Gets the current [`Rotation`] of the view
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Setter for the [`RenderMode`] of the view. Defaults to
+    /// [`RenderMode::HalfBlock`].
+    pub fn set_mode(&mut self, mode: RenderMode) {
+        self.mode = mode;
+    }
+
+    /// Gets the current [`RenderMode`] of the view
+    pub fn mode(&self) -> RenderMode {
+        self.mode
+    }
+
+    /// Setter for the [`Resample`] filter used when the view is scaled to the
+    /// render area. Defaults to [`Resample::Nearest`].
+    ///
+    /// The filter trades quality for speed: [`Resample::Nearest`] is the
+    /// fastest, but causes jagged edges when upscaling and aliasing when
+    /// downscaling, which the other filters reduce by taking more than one
+    /// image pixel into account. The filter is not used on the exact-size path
+    /// (see [`is_exact`](ImageView::is_exact)), where no scaling is needed.
+    pub fn set_resample(&mut self, resample: Resample) {
+        self.resample = resample;
+    }
+
+    /// Gets the current [`Resample`] filter of the view
+    pub fn resample(&self) -> Resample {
+        self.resample
+    }
+
+    /// Setter for automatically choosing the [`Resample`] filter. Disabled
+    /// ([`None`]) by default, in which case the filter set with
+    /// [`set_resample`](ImageView::set_resample) is always used.
+    ///
+    /// When enabled, the filter is chosen each time the view is rendered, from
+    /// how much the image is scaled down to fit the area: the filter set with
+    /// [`set_resample`](ImageView::set_resample) (by default
+    /// [`Resample::Nearest`]) is used when upscaling or mildly downscaling,
+    /// where it is fast and looks fine, and [`Resample::Average`] is used when
+    /// more than `crossover` image pixels map to one sampled pixel along either
+    /// axis, where nearest-neighbor aliasing is worst. The scale is measured
+    /// against the pixel grid sampled by the [`RenderMode`], so e.g.
+    /// [`RenderMode::Braille`] switches later than [`RenderMode::HalfBlock`].
+    ///
+    /// [`Resample::DEFAULT_CROSSOVER`] (`1.5`) is a good default. Use
+    /// [`resample_for`](ImageView::resample_for) to see which filter will be
+    /// used for a given area.
+    pub fn set_auto_resample(&mut self, crossover: Option<f32>) {
+        self.auto_resample = crossover.map(f32::to_bits);
+    }
+
+    /// Gets the crossover factor for automatically choosing the [`Resample`]
+    /// filter, if enabled
+    pub fn auto_resample(&self) -> Option<f32> {
+        self.auto_resample.map(f32::from_bits)
+    }
+
+    /// Setter for the fractional part of the origin of the [`Region`] of the
+    /// view, in image pixels. Each component is clamped to the range `0.0` to
+    /// `1.0`. Defaults to `(0.0, 0.0)`.
+    ///
+    /// This shifts the rendered image by a fraction of a pixel, so panning by
+    /// moving the region one pixel at a time can be smoothed by stepping the
+    /// offset in between, even when the image is rendered at its exact size.
+    /// A non-zero offset interpolates between pixels, so [`Resample::Nearest`]
+    /// is replaced by [`Resample::Bilinear`] while it is set, and the
+    /// exact-size path is not taken. Pixels shifted in past the edge of the
+    /// region repeat the edge pixels.
+    pub fn set_subpixel_offset(&mut self, x: f32, y: f32) {
+        // Stored as bits, with negative zero normalized, so that the view
+        // can be `Eq` and `Hash`
+        let clamp = |val: f32| {
+            let val = if val.is_nan() { 0.0 } else { val.clamp(0.0, 1.0) };
+            (val + 0.0).to_bits()
+        };
+        self.subpixel_offset = (clamp(x), clamp(y));
+    }
+
+    /// Gets the fractional part of the origin of the [`Region`] of the view
+    pub fn subpixel_offset(&self) -> (f32, f32) {
+        let (x, y) = self.subpixel_offset;
+        (f32::from_bits(x), f32::from_bits(y))
+    }
+
+    /// Setter for a callback applied to each pixel of the view as it is
+    /// sampled, or [`None`] to disable it. Defaults to [`None`].
+    ///
+    /// The callback receives the coordinates of the pixel relative to the
+    /// [`Region`] (like [`pixel`](ImageView::pixel)) and its color, and
+    /// returns the color to render. It is applied before resampling, blending
+    /// with the background, and other render options, so it can implement
+    /// effects depending on the pixel coordinates (e.g. scanlines, a selection
+    /// highlight, or a crosshair) without modifying or copying the image.
+    ///
+    /// The callback runs in the inner loop of every render, once for each
+    /// sampled image pixel (several times per pixel with filters other than
+    /// [`Resample::Nearest`]), so it should be cheap. Views with a callback are
+    /// never cached by [`ImageCache`], since the callback may change between
+    /// frames.
+    pub fn set_pixel_hook(&mut self, hook: Option<&'a PixelHookFn>) {
+        self.pixel_hook = hook.map(PixelHook);
+    }
+
+    /// Setter for the [`SampleMode`] of the view, selecting what is drawn at
+    /// positions of the render area beyond the [`Region`], such as the
+    /// letterbox of [`Fit::Zoom`] and the top half of the first row with a
+    /// half cell offset. Defaults to [`SampleMode::None`], which leaves them
+    /// undrawn.
+    ///
+    /// With smooth edges enabled (see
+    /// [`set_smooth_edges`](ImageView::set_smooth_edges)), pixels beyond the
+    /// region are still faded out. Resampling filters combining several image
+    /// pixels always repeat the edge pixels of the region, regardless of the
+    /// sample mode.
+    pub fn set_sample_mode(&mut self, mode: SampleMode) {
+        self.sample_mode = mode;
+    }
+
+    /// Gets the [`SampleMode`] of the view
+    pub fn sample_mode(&self) -> SampleMode {
+        self.sample_mode
+    }
+
+    /// Setter for the [`LumaWeights`] used by the render modes choosing
+    /// characters by brightness: [`RenderMode::Braille`],
+    /// [`RenderMode::Ascii`], and [`RenderMode::Monochrome`]. Defaults to
+    /// [`LumaWeights::Rec601`].
+    pub fn set_luma_weights(&mut self, weights: LumaWeights) {
+        self.luma_weights = weights;
+    }
+
+    /// Gets the [`LumaWeights`] used by render modes based on brightness
+    pub fn luma_weights(&self) -> LumaWeights {
+        self.luma_weights
+    }
+
+    /// Setter for the characters [`RenderMode::Ascii`] and
+    /// [`RenderMode::Monochrome`] choose from by brightness, from darkest to
+    /// brightest, e.g. `" .oO@"` or `" ░▒▓█"`. The luminance range is split
+    /// evenly between the characters. An empty ramp falls back to the
+    /// default of `" .:-=+*#%@"`. For a ramp matching the perceived
+    /// brightness of modern displays, combine it with
+    /// [`LumaWeights::Rec709`].
+    pub fn set_ascii_ramp(&mut self, ramp: &'a str) {
+        self.ascii_ramp = ramp;
+    }
+
+    /// Gets the characters used by the character based render modes
+    pub fn ascii_ramp(&self) -> &'a str {
+        self.ascii_ramp
+    }
+
+    /// Setter for the [`ColorDepth`] the colors of rendered cells are reduced
+    /// to, so images display correctly on terminals without 24-bit color
+    /// support. Defaults to [`ColorDepth::TrueColor`], leaving colors
+    /// unchanged. [`ColorDepth::Auto`] detects the color depth from the
+    /// environment (see [`detect_color_depth`]). Colors of the letterbox and
+    /// border that are not RGB are left unchanged.
+    pub fn set_color_depth(&mut self, depth: ColorDepth) {
+        self.color_depth = depth;
+    }
+
+    /// Gets the [`ColorDepth`] of the view
+    pub fn color_depth(&self) -> ColorDepth {
+        self.color_depth
+    }
+
+    /// Setter for whether colors are dithered when reduced to a
+    /// [`ColorDepth`] other than [`ColorDepth::TrueColor`]. Defaults to
+    /// `false`.
+    ///
+    /// When enabled, Floyd–Steinberg error diffusion is applied across all
+    /// pixels sampled for the render area before cells are built, so smooth
+    /// gradients show as a fine pattern of palette colors instead of flat
+    /// bands. This costs an extra pass over the sampled pixels on every
+    /// render, and the pattern changes whenever the image moves or is
+    /// resized, which may be distracting for animations.
+    pub fn set_dither(&mut self, dither: bool) {
+        self.dither = dither;
+    }
+
+    /// Gets whether colors are dithered when reduced to the [`ColorDepth`]
+    pub fn dither(&self) -> bool {
+        self.dither
+    }
+
+    /// Setter for the [`Protocol`] used to draw the view. Defaults to
+    /// [`Protocol::HalfBlock`].
+    ///
+    /// With [`Protocol::Sixel`], the image is encoded at the pixel size of the
+    /// area (see [`set_font_size`](ImageView::set_font_size)), with colors
+    /// quantized to a palette of 256 colors. The escape sequence is written as
+    /// the symbol of the top-left cell of the image, and the other cells it
+    /// covers are marked as skipped (see [`Cell::set_skip`]), so the backend
+    /// writes the sequence once and does not draw over the image. Letterbox
+    /// pixels are left transparent, and the [`RenderMode`] and
+    /// [`ColorDepth`] of the view are not used. Only rendering the view as a
+    /// [`Widget`] (including [`render_with_stats`](ImageView::render_with_stats)
+    /// and [`ImageCache`]) uses the protocol; [`cells`](ImageView::cells),
+    /// [`Crossfade`], and canvas drawing always draw text cells.
+    pub fn set_protocol(&mut self, protocol: Protocol) {
+        self.protocol = protocol;
+    }
+
+    /// Gets the [`Protocol`] of the view
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Setter for the size of a terminal cell in pixels, which graphics
+    /// protocols need to draw the image at the resolution of the terminal.
+    /// Defaults to 8 by 16 pixels. Sizes of `0` are treated as `1`.
+    ///
+    /// The actual size depends on the font and the terminal, and can be
+    /// queried e.g. with `crossterm::terminal::window_size`, dividing the
+    /// window size in pixels by its size in cells.
+    pub fn set_font_size(&mut self, width: u16, height: u16) {
+        self.font_size = (width, height);
+    }
+
+    /// Gets the size of a terminal cell in pixels, as (width, height)
+    pub fn font_size(&self) -> (u16, u16) {
+        self.font_size
+    }
+
+    /// Setter for the color of a border drawn around the rendered image, or
+    /// [`None`] for no border. Defaults to [`None`].
+    ///
+    /// The border is drawn with single line box-drawing characters, like a
+    /// plain [`Block`](ratatui::widgets::Block). The image is rendered into the
+    /// area shrunk by one cell on each side, leaving room for the border, and
+    /// the border hugs the image as drawn rather than the area, so with
+    /// [`Fit::Zoom`] it is inside of the letterbox. [`Crossfade`] does not
+    /// draw borders.
+    pub fn set_border(&mut self, color: Option<Color>) {
+        self.border = color;
+    }
+
+    /// Gets the color of the border drawn around the rendered image, if any
+    pub fn border(&self) -> Option<Color> {
+        self.border
+    }
+
+    /// Setter for the [`Cvd`] (color blindness) simulated by the view.
+    /// Defaults to [`Cvd::None`].
+    ///
+    /// The colors of the image are transformed as they are rendered, after
+    /// resampling and before blending with the background, so the image itself
+    /// is not modified. The background and letterbox colors are not
+    /// transformed.
+    pub fn set_cvd(&mut self, cvd: Cvd) {
+        self.cvd = cvd;
+    }
+
+    /// Gets the [`Cvd`] (color blindness) simulated by the view
+    pub fn cvd(&self) -> Cvd {
+        self.cvd
+    }
+
+    /// Gets the [`Resample`] filter used when rendering into `area`
+    pub fn resample_for(&self, area: Rect) -> Resample {
+        self.mapping(area).resample
+    }
+
+    /// Gets the original image
+    ///
+    /// # Panics
+    ///
+    /// Panics if the view was created from raw pixels with
+    /// [`from_raw`](ImageView::from_raw). Use
+    /// [`try_image`](ImageView::try_image) for views that may be.
+    pub fn image(&self) -> &'a Image {
+        self.image.expect("view was created from raw pixels, not an Image")
+    }
+
+    /// Gets the original image, or [`None`] if the view was created from raw
+    /// pixels with [`from_raw`](ImageView::from_raw)
+    pub fn try_image(&self) -> Option<&'a Image> {
+        self.image
+    }
+
+    /// Gets the current [`Fit`] mode of the view
+    pub fn fit(&self) -> Fit {
+        self.fit
+    }
+
+    /// Gets the current [`Region`] of the view
+    pub fn region(&self) -> &Region {
+        &self.region
+    }
+
+    /// The width of the view, in pixels. This is the width of its [`Region`],
+    /// which is the coordinate space of [`pixel`](ImageView::pixel).
+    pub const fn width(&self) -> usize {
+        self.region.width
+    }
+
+    /// The height of the view, in pixels. This is the height of its
+    /// [`Region`], which is the coordinate space of
+    /// [`pixel`](ImageView::pixel).
+    pub const fn height(&self) -> usize {
+        self.region.height
+    }
+
+    /// Returns a new view of a sub-region of this view, with the same render
+    /// options, for composing crops of crops.
+    ///
+    /// The coordinates of `region` are relative to the [`Region`] of this
+    /// view, not to the image. The resulting region is the intersection of
+    /// the two, so a `region` extending beyond this view is clamped to its
+    /// bounds.
+    pub fn subview(&self, region: Region) -> ImageView<'a> {
+        let region = region.clamped(self.region.width, self.region.height);
+        ImageView {
+            region: Region {
+                x: self.region.x + region.x,
+                y: self.region.y + region.y,
+                ..region
+            },
+            ..*self
+        }
+    }
+
+    /// Checks whether the pixel coordinates, relative to the [`Region`] of the
+    /// view, are within its bounds
+    pub const fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.region.width && y < self.region.height
+    }
+
+    /// Gets the current [`Checkerboard`] background of the view, if any
+    pub fn checkerboard_bg(&self) -> Option<Checkerboard> {
+        self.checkerboard
+    }
+
+    /// Gets the current [`ClipShape`] of the view
+    pub fn clip_shape(&self) -> ClipShape {
+        self.clip_shape
+    }
+
+    /// Checks whether rendering into `area` will take the exact-size path.
+    ///
+    /// When the area is exactly [`Region::cell_width`] cells wide and the
+    /// region height is exactly twice the area height, or one less with a
+    /// half cell offset, both measured after [`Rotation`], each pixel maps to
+    /// one half of a cell with no scaling, producing crisp output, unless a
+    /// subpixel offset is set (see
+    /// [`set_subpixel_offset`](ImageView::set_subpixel_offset)). Otherwise, the
+    /// region is scaled to the area according to the [`Fit`] mode. Callers
+    /// wanting pixel-perfect output can use this to decide whether to resize
+    /// the image ahead of time to match the area. With a border (see
+    /// [`set_border`](ImageView::set_border)), the area must be one cell larger
+    /// on each side.
+    pub fn is_exact(&self, area: Rect) -> bool {
+        let area = self.image_area(area);
+        let (width, height) = self.oriented_size();
+        area.width as usize == width
+            && area.height as usize * 2 == height + self.half_cell_offset as usize
+            && self.subpixel_offset == (0, 0)
+    }
+
+    /// Renders the view into a new standalone [`Buffer`] of `width` by `height`
+    /// cells, with its origin at (0, 0).
+    ///
+    /// This is useful for capturing the rendered output without a terminal,
+    /// such as for snapshot testing or off-screen composition.
+    pub fn render_to_buffer(&self, width: u16, height: u16) -> Buffer {
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        };
+        let mut buf = Buffer::empty(area);
+        self.render(area, &mut buf);
+        buf
+    }
+
+    /// Renders the view into `area` of `buf` like the [`Widget`]
+    /// implementation, returning [`RenderStats`] about what was done.
+    ///
+    /// This is useful for tuning layouts and [`Resample`] choices, such as for
+    /// checking whether an area takes the exact-size path, or how much the
+    /// image is scaled.
+    pub fn render_with_stats(&self, area: Rect, buf: &mut Buffer) -> RenderStats {
+        let mapping = self.mapping(area);
+        if self.protocol == Protocol::Sixel {
+            return RenderStats {
+                cells_written: self.render_graphics(&mapping, area, buf, sixel::encode),
+                exact: mapping.exact,
+                zoom_x: mapping.zoom_x,
+                zoom_y: mapping.zoom_y,
+                resample: mapping.resample,
+            };
+        }
+        let dithered = self.dithered_samples(&mapping, area);
+        let ramp = mode::Ramp::new(self.ascii_ramp);
+        let mut samples = [None; mode::MAX_SAMPLES];
+        let mut cells_written = 0;
+        fill_area(area, buf, |x, y, cell| {
+            if self.draw_border(&mapping, x, y, cell) {
+                cells_written += 1;
+                return;
+            }
+            let samples = match &dithered {
+                Some(grid) => self.grid_cell(grid, area.width, x, y, &mut samples),
+                None => self.sample_grid(&mapping, x, y, &mut samples),
+            };
+            let (bg, luma) = (self.bg, self.luma_weights);
+            if write_cell(cell, self.mode, samples, bg, luma, ramp, self.clear) {
+                reduce_colors(cell, mapping.color_depth);
+                cells_written += 1;
+            }
+        });
+        RenderStats {
+            cells_written,
+            exact: mapping.exact,
+            zoom_x: mapping.zoom_x,
+            zoom_y: mapping.zoom_y,
+            resample: mapping.resample,
+        }
+    }
+
+    /// Returns an iterator over the cells the view renders into `area`, without
+    /// writing them to a [`Buffer`].
+    ///
+    /// Each item is the position of the cell (in the same coordinates as
+    /// `area`) and the cell itself, in row-major order. The cells are the same
+    /// as [`render`](Widget::render) would write, assuming the buffer was
+    /// empty, so they can be post-processed, placed in a larger composited
+    /// buffer, or inspected in tests. With clearing disabled (see
+    /// [`set_clear`](ImageView::set_clear)), cells the image does not cover
+    /// are skipped. Cells are computed lazily as the iterator is advanced,
+    /// except that pixels are sampled up front when dithering (see
+    /// [`set_dither`](ImageView::set_dither)).
+    ///
+    /// With [`Protocol::Sixel`], the image is encoded up front, and like with
+    /// [`render`](Widget::render), the escape sequence is the symbol of the
+    /// top-left cell of the image, and the other cells it covers are marked
+    /// as skipped. Letterbox cells are never yielded, since they are not
+    /// written.
+    pub fn cells(&self, area: Rect) -> impl Iterator<Item = (u16, u16, Cell)> + 'a {
+        let view = *self;
+        let mapping = self.mapping(area);
+        let graphics = (self.protocol == Protocol::Sixel).then(|| {
+            let mut buf = Buffer::empty(area);
+            self.render_graphics(&mapping, area, &mut buf, sixel::encode);
+            (buf, self.image_area(area).intersection(area))
+        });
+        let dithered = match graphics {
+            Some(_) => None,
+            None => self.dithered_samples(&mapping, area),
+        };
+        let ramp = mode::Ramp::new(self.ascii_ramp);
+        (0..area.height)
+            .flat_map(move |y| (0..area.width).map(move |x| (x, y)))
+            .filter_map(move |(x, y)| {
+                let mut cell = Cell::default();
+                if view.draw_border(&mapping, x, y, &mut cell) {
+                    return Some((area.x + x, area.y + y, cell));
+                }
+                if let Some((buf, inner)) = &graphics {
+                    let (x, y) = (area.x + x, area.y + y);
+                    let covered = (inner.left()..inner.right()).contains(&x)
+                        && (inner.top()..inner.bottom()).contains(&y);
+                    return covered.then(|| (x, y, buf.get(x, y).clone()));
+                }
+                let mut samples = [None; mode::MAX_SAMPLES];
+                let samples = match &dithered {
+                    Some(grid) => view.grid_cell(grid, area.width, x, y, &mut samples),
+                    None => view.sample_grid(&mapping, x, y, &mut samples),
+                };
+                let (bg, luma) = (view.bg, view.luma_weights);
+                write_cell(&mut cell, view.mode, samples, bg, luma, ramp, view.clear).then(|| {
+                    reduce_colors(&mut cell, mapping.color_depth);
+                    (area.x + x, area.y + y, cell)
+                })
+            })
+    }
+
+    /// Returns an iterator over the pixels of the view according to its [`Region`]
+    pub fn pixels(&self) -> ViewPixels<'a> {
+        ViewPixels {
+            pixels: self.pixels,
+            region: self.region,
+            real_width: self.width,
+            x: self.region.x,
+            y: self.region.y,
+        }
+    }
+
+    /// Gets the pixel value at given pixel coordinates. [`None`](std::option::Option)
+    /// is returned if the coordinates are out of bounds.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Pixel> {
+        if !self.contains(x, y) {
+            None
+        } else {
+            self.pixels
+                .get((y + self.region.y) * self.width + x + self.region.x)
+                .copied()
+        }
+    }
+}
+
+const PIXEL_CHAR: char = '▀';
+const LOWER_PIXEL_CHAR: char = '▄';
+
+/// Precomputed mapping from the cells of a render area to the pixels of an
+/// [`ImageView`].
+#[derive(Debug, Clone, Copy)]
+struct Mapping {
+    empty: bool,
+    exact: bool,
+    zoom_x: f32,
+    zoom_y: f32,
+    /// The zoom factors, if both are integers and the image is sampled with
+    /// [`Resample::Nearest`], so pixels can be mapped without float division
+    integer_zoom: Option<(isize, isize)>,
+    left: usize,
+    top: usize,
+    bounds: (f32, f32, f32, f32),
+    resample: Resample,
+    /// The subpixel offset of the view after rotation and flips
+    shift: (f32, f32),
+    /// Whether the image is known to be fully opaque, so sampled pixels never
+    /// need to be blended
+    opaque: bool,
+    /// The left, top, right, and bottom cells of the border, relative to the
+    /// render area, if there is one
+    border: Option<(u16, u16, u16, u16)>,
+    /// The color depth of the view, with [`ColorDepth::Auto`] resolved
+    color_depth: ColorDepth,
+}
+
+impl Mapping {
+    /// The fraction of the pixel at `x`, `y` relative to the render area that
+    /// is covered by the scaled image.
+    fn coverage(&self, x: usize, y: usize) -> f32 {
+        let (left, top, right, bottom) = self.bounds;
+        let (x, y) = (x as f32, y as f32);
+        let width = (right.min(x + 1.0) - left.max(x)).clamp(0.0, 1.0);
+        let height = (bottom.min(y + 1.0) - top.max(y)).clamp(0.0, 1.0);
+        width * height
+    }
+}
+
+impl<'a> ImageView<'a> {
+    /// Blends a pixel with the checkerboard background, if there is one, at
+    /// the given pixel coordinates relative to the render area.
+    fn backdrop(&self, pix: Option<Pixel>, x: u16, y: usize) -> Option<Pixel> {
+        match self.checkerboard {
+            Some(checkerboard) => pix.map(|pix| pix.over(checkerboard.color_at(x as usize, y))),
+            None => pix,
+        }
+    }
+
+    /// The size of the region after rotation
+    fn oriented_size(&self) -> (usize, usize) {
+        match self.rotation {
+            Rotation::None | Rotation::Clockwise180 => (self.region.width, self.region.height),
+            Rotation::Clockwise90 | Rotation::Clockwise270 => {
+                (self.region.height, self.region.width)
+            }
+        }
+    }
+
+    /// Gets the pixel at the given coordinates of the region after rotation
+    /// and flips are applied.
+    fn oriented_pixel(&self, x: usize, y: usize) -> Option<Pixel> {
+        let (width, height) = self.oriented_size();
+        if x >= width || y >= height {
+            return None;
+        }
+        let x = if self.flip_horizontal { width - 1 - x } else { x };
+        let y = if self.flip_vertical { height - 1 - y } else { y };
+        let (x, y) = match self.rotation {
+            Rotation::None => (x, y),
+            Rotation::Clockwise90 => (y, self.region.height - 1 - x),
+            Rotation::Clockwise180 => (self.region.width - 1 - x, self.region.height - 1 - y),
+            Rotation::Clockwise270 => (self.region.width - 1 - y, x),
+        };
+        let pix = self.pixel(x, y)?;
+        Some(match self.pixel_hook {
+            Some(PixelHook(hook)) => hook(x, y, pix),
+            None => pix,
+        })
+    }
+
+    /// The subpixel offset of the view, in the coordinates of the region after
+    /// rotation and flips.
+    fn oriented_subpixel_offset(&self) -> (f32, f32) {
+        let (x, y) = self.subpixel_offset();
+        let (x, y) = match self.rotation {
+            Rotation::None => (x, y),
+            Rotation::Clockwise90 => (-y, x),
+            Rotation::Clockwise180 => (-x, -y),
+            Rotation::Clockwise270 => (y, -x),
+        };
+        let x = if self.flip_horizontal { -x } else { x };
+        let y = if self.flip_vertical { -y } else { y };
+        (x, y)
+    }
+
+    /// The part of `area` the image is rendered into, inside of the border if
+    /// there is one
+    fn image_area(&self, area: Rect) -> Rect {
+        match self.border {
+            Some(_) => area.inner(Margin::new(1, 1)),
+            None => area,
+        }
+    }
+
+    fn mapping(&self, area: Rect) -> Mapping {
+        let exact = self.is_exact(area);
+        let inset = self.border.is_some() as usize;
+        let area = self.image_area(area);
+        let offset = self.half_cell_offset as usize;
+        let height = (area.height as usize * 2).saturating_sub(offset);
+        let (region_width, region_height) = self.oriented_size();
+        let mut mapping = Mapping {
+            empty: false,
+            exact,
+            zoom_x: area.width as f32 / region_width as f32,
+            zoom_y: height as f32 / region_height as f32,
+            integer_zoom: None,
+            left: inset,
+            top: offset + inset * 2,
+            bounds: (0.0, 0.0, 0.0, 0.0),
+            resample: self.resample,
+            shift: self.oriented_subpixel_offset(),
+            opaque: self.pixel_hook.is_none()
+                && self.image.is_some_and(|image| !image.has_alpha()),
+            border: None,
+            color_depth: self.color_depth.resolve(),
+        };
+        if region_width == 0 || region_height == 0 || area.width == 0 || height == 0 {
+            // Nothing of the image can be drawn, and the zoom factors are
+            // meaningless (zero, infinite, or NaN), so every cell is letterbox.
+            mapping.empty = true;
+            mapping.zoom_x = 0.0;
+            mapping.zoom_y = 0.0;
+            return mapping;
+        }
+        if !mapping.exact {
+            if let Fit::Zoom = self.fit {
+                if mapping.zoom_x < mapping.zoom_y {
+                    mapping.top +=
+                        ((height - (region_height as f32 * mapping.zoom_x) as usize) / 4) * 2;
+                    mapping.zoom_y = mapping.zoom_x;
+                } else {
+                    mapping.left += (area.width as usize
+                        - (region_width as f32 * mapping.zoom_y) as usize)
+                        / 2;
+                    mapping.zoom_x = mapping.zoom_y;
+                }
+            }
+        }
+        let left = mapping.left as f32;
+        let top = mapping.top as f32;
+        mapping.bounds = (
+            left,
+            top,
+            left + region_width as f32 * mapping.zoom_x,
+            top + region_height as f32 * mapping.zoom_y,
+        );
+        if let Some(crossover) = self.auto_resample() {
+            if !mapping.exact {
+                // Number of image pixels per sampled pixel along each axis
+                let (cols, rows) = self.mode.grid();
+                let scale_x = 1.0 / (mapping.zoom_x * cols as f32);
+                let scale_y = 2.0 / (mapping.zoom_y * rows as f32);
+                if scale_x.max(scale_y) > crossover {
+                    mapping.resample = Resample::Average;
+                }
+            }
+        }
+        if mapping.shift != (0.0, 0.0) && mapping.resample == Resample::Nearest {
+            mapping.resample = Resample::Bilinear;
+        }
+        if !mapping.exact && mapping.resample == Resample::Nearest {
+            // Both factors are exact in f32 when the sizes divide evenly
+            let (zoom_x, zoom_y) = (mapping.zoom_x as isize, mapping.zoom_y as isize);
+            if zoom_x >= 1
+                && zoom_y >= 1
+                && zoom_x as f32 == mapping.zoom_x
+                && zoom_y as f32 == mapping.zoom_y
+            {
+                mapping.integer_zoom = Some((zoom_x, zoom_y));
+            }
+        }
+        if self.border.is_some() {
+            // The image is inside of the border, so it never starts at the
+            // first row or column, and the border never ends past the last.
+            let (left, top, right, bottom) = mapping.bounds;
+            mapping.border = Some((
+                left.floor() as u16 - 1,
+                (top / 2.0).floor() as u16 - 1,
+                (right.ceil() as u16).min(area.width + 1),
+                ((bottom / 2.0).ceil() as u16).min(area.height + 1),
+            ));
+        }
+        mapping
+    }
+
+    /// Draws the border into `cell` if the cell at `x`, `y` relative to the
+    /// render area is part of it, returning whether it was drawn
+    fn draw_border(&self, mapping: &Mapping, x: u16, y: u16, cell: &mut Cell) -> bool {
+        let (Some(color), Some((left, top, right, bottom))) = (self.border, mapping.border) else {
+            return false;
+        };
+        if x < left || x > right || y < top || y > bottom {
+            return false;
+        }
+        let set = symbols::border::PLAIN;
+        let symbol = match (x == left, x == right, y == top, y == bottom) {
+            (true, _, true, _) => set.top_left,
+            (_, true, true, _) => set.top_right,
+            (true, _, _, true) => set.bottom_left,
+            (_, true, _, true) => set.bottom_right,
+            (true, _, _, _) => set.vertical_left,
+            (_, true, _, _) => set.vertical_right,
+            (_, _, true, _) => set.horizontal_top,
+            (_, _, _, true) => set.horizontal_bottom,
+            _ => return false,
+        };
+        if self.clear {
+            *cell = Cell::default();
+        }
+        cell.set_symbol(symbol).set_fg(color);
+        true
+    }
+
+    /// Renders the view into `area` of `buf` with a graphics protocol,
+    /// returning the number of cells written.
+    ///
+    /// The pixels of the image area are sampled at the font size of the view,
+    /// composited over the background color (letterbox pixels are [`None`]),
+    /// and passed to `encode` along with their width and height. The returned
+    /// escape sequence is written as the symbol of the top-left cell of the
+    /// image, and the other cells covered by the image are marked as skipped,
+    /// so the backend does not draw over the image. The border, if any, is
+    /// drawn with text cells.
+    fn render_graphics<F>(&self, mapping: &Mapping, area: Rect, buf: &mut Buffer, encode: F) -> usize
+    where
+        F: FnOnce(&[Option<Pixel>], usize, usize) -> String,
+    {
+        let mut cells_written = 0;
+        if self.border.is_some() {
+            fill_area(area, buf, |x, y, cell| {
+                if self.draw_border(mapping, x, y, cell) {
+                    cells_written += 1;
                 }
+            });
+        }
+        let inner = self.image_area(area).intersection(buf.area);
+        if inner.is_empty() {
+            return cells_written;
+        }
+
+        let (font_width, font_height) = self.font_size;
+        let (font_width, font_height) = (font_width.max(1) as usize, font_height.max(1) as usize);
+        let width = inner.width as usize * font_width;
+        let height = inner.height as usize * font_height;
+        let size = (1.0 / font_width as f32, 2.0 / font_height as f32);
+        let left = (inner.x - area.x) as f32;
+        let top = (inner.y - area.y) as f32 * 2.0;
+        let mut pixels = Vec::with_capacity(width * height);
+        for py in 0..height {
+            for px in 0..width {
+                let x = left + px as f32 * size.0;
+                let y = top + py as f32 * size.1;
+                let pix = self.sample_point(mapping, x, y, size);
+                pixels.push(pix.map(|pix| pix.over(self.bg)));
+            }
+        }
+
+        let sequence = encode(&pixels, width, height);
+        fill_area(inner, buf, |x, y, cell| {
+            if x == 0 && y == 0 {
+                cell.reset();
+                cell.set_symbol(&sequence);
+            } else {
+                cell.set_skip(true);
+            }
+            cells_written += 1;
+        });
+        cells_written
+    }
+
+    /// Applies the clip shape, color blindness simulation, transparency
+    /// passthrough, and backdrop to a sampled pixel at the given pixel
+    /// coordinates relative to the render area.
+    fn finish(&self, mapping: &Mapping, pix: Option<Pixel>, x: u16, y: usize) -> Option<Pixel> {
+        if !self.clip_shape.contains(x as usize, y, mapping.bounds) {
+            return None;
+        }
+        let pix = pix.map(|pix| self.cvd.apply(pix));
+        if mapping.opaque && !self.smooth_edges {
+            return pix;
+        }
+        let pix = match pix {
+            Some(pix) if self.transparency_passthrough && pix.a == 0 => None,
+            pix => pix,
+        };
+        let pix = match pix {
+            Some(pix) if self.smooth_edges => Some(pix.faded(mapping.coverage(x as usize, y))),
+            pix => pix,
+        };
+        self.backdrop(pix, x, y)
+    }
+
+    /// Samples the pixel at the possibly fractional position `x`, `y` relative
+    /// to the render area, in units of half-block pixels (i.e. a cell is 1 wide
+    /// and 2 tall). The sampled pixel is `size` units wide and tall, which
+    /// determines the image pixels it covers when resampling.
+    fn sample_point(&self, mapping: &Mapping, x: f32, y: f32, size: (f32, f32)) -> Option<Pixel> {
+        if mapping.empty {
+            return None;
+        }
+        let off_x = x - mapping.left as f32;
+        let off_y = y - mapping.top as f32;
+        let (width, height) = self.oriented_size();
+        let pix = if mapping.exact {
+            let x = self.sample_mode.index(off_x.floor() as isize, width);
+            let y = self.sample_mode.index(off_y.floor() as isize, height);
+            x.zip(y).and_then(|(x, y)| self.oriented_pixel(x, y))
+        } else if let Some((zoom_x, zoom_y)) = mapping.integer_zoom {
+            // Each image pixel covers exactly `zoom_x` by `zoom_y` pixels, so
+            // the pixel under the center of the sample is found by integer
+            // division of the rendered pixel it falls in
+            let center_x = (off_x + size.0 / 2.0).floor() as isize;
+            let center_y = (off_y + size.1 / 2.0).floor() as isize;
+            let x = self.sample_mode.index(center_x.div_euclid(zoom_x), width);
+            let y = self.sample_mode.index(center_y.div_euclid(zoom_y), height);
+            x.zip(y).and_then(|(x, y)| self.oriented_pixel(x, y))
+        } else {
+            let area = (
+                off_x / mapping.zoom_x,
+                off_y / mapping.zoom_y,
+                (off_x + size.0) / mapping.zoom_x,
+                (off_y + size.1) / mapping.zoom_y,
+            );
+            // A pixel is only drawn if its center is within the image, after
+            // applying the sample mode
+            let center_x = (area.0 + area.2) / 2.0;
+            let center_y = (area.1 + area.3) / 2.0;
+            let x = self.sample_mode.position(center_x, width);
+            let y = self.sample_mode.position(center_y, height);
+            x.zip(y).map(|(x, y)| {
+                let shift_x = x - center_x + mapping.shift.0;
+                let shift_y = y - center_y + mapping.shift.1;
+                let area = (area.0 + shift_x, area.1 + shift_y, area.2 + shift_x, area.3 + shift_y);
+                let src = |x, y| self.oriented_pixel(x, y).unwrap_or_default();
+                mapping.resample.sample(src, width, height, area)
+            })
+        };
+        self.finish(mapping, pix, x as u16, y as usize)
+    }
+
+    /// Samples the pixel at `x`, `y` relative to the render area
+    fn sample_pixel(&self, mapping: &Mapping, x: u16, y: usize) -> Option<Pixel> {
+        self.sample_point(mapping, x as f32, y as f32, (1.0, 1.0))
+    }
+
+    /// Samples the grid of pixels of the current [`RenderMode`] for the cell at
+    /// `x`, `y` relative to the render area, in row-major order.
+    fn sample_grid<'s>(
+        &self,
+        mapping: &Mapping,
+        x: u16,
+        y: u16,
+        samples: &'s mut [Option<Pixel>; mode::MAX_SAMPLES],
+    ) -> &'s [Option<Pixel>] {
+        let (cols, rows) = self.mode.grid();
+        let samples = &mut samples[..(cols * rows)];
+        let size = (1.0 / cols as f32, 2.0 / rows as f32);
+        for (idx, sample) in samples.iter_mut().enumerate() {
+            let sub_x = (idx % cols) as f32 * size.0;
+            let sub_y = (idx / cols) as f32 * size.1;
+            *sample = self.sample_point(mapping, x as f32 + sub_x, y as f32 * 2.0 + sub_y, size);
+        }
+        samples
+    }
+
+    /// Samples the grids of all cells of `area` into a single grid of opaque
+    /// pixels in row-major order, and dithers it to the color depth of
+    /// `mapping`.
+    /// Returns [`None`] if dithering is disabled or not needed.
+    fn dithered_samples(&self, mapping: &Mapping, area: Rect) -> Option<Vec<Option<Pixel>>> {
+        if !self.dither || mapping.color_depth == ColorDepth::TrueColor {
+            return None;
+        }
+        let (cols, rows) = self.mode.grid();
+        let width = area.width as usize * cols;
+        let height = area.height as usize * rows;
+        let mut grid = vec![None; width * height];
+        let mut samples = [None; mode::MAX_SAMPLES];
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let samples = self.sample_grid(mapping, x, y, &mut samples);
+                for (idx, sample) in samples.iter().enumerate() {
+                    let row = y as usize * rows + idx / cols;
+                    // Dither the colors as drawn, after blending with the
+                    // background, so the error of translucent pixels is right
+                    let sample = sample.map(|pix| pix.over(self.bg));
+                    grid[row * width + x as usize * cols + idx % cols] = sample;
+                }
+            }
+        }
+        mapping.color_depth.dither(&mut grid, width, height);
+        Some(grid)
+    }
+
+    /// Gets the samples of the cell at `x`, `y` relative to the render area of
+    /// `area_width` cells, from a grid returned by
+    /// [`dithered_samples`](ImageView::dithered_samples).
+    fn grid_cell<'s>(
+        &self,
+        grid: &[Option<Pixel>],
+        area_width: u16,
+        x: u16,
+        y: u16,
+        samples: &'s mut [Option<Pixel>; mode::MAX_SAMPLES],
+    ) -> &'s [Option<Pixel>] {
+        let (cols, rows) = self.mode.grid();
+        let width = area_width as usize * cols;
+        let samples = &mut samples[..(cols * rows)];
+        for (idx, sample) in samples.iter_mut().enumerate() {
+            let row = y as usize * rows + idx / cols;
+            *sample = grid[row * width + x as usize * cols + idx % cols];
+        }
+        samples
+    }
+
+    /// Samples the top and bottom pixels of the cell at `x`, `y` relative to
+    /// the render area.
+    fn sample(&self, mapping: &Mapping, x: u16, y: u16) -> (Option<Pixel>, Option<Pixel>) {
+        let y1 = y as usize * 2;
+        let y2 = y1 + 1;
+        (
+            self.sample_pixel(mapping, x, y1),
+            self.sample_pixel(mapping, x, y2),
+        )
+    }
+}
+
+/// Builds the terminal cell for a vertically stacked pair of pixels.
+///
+/// The `top` pixel is drawn as the foreground of an upper half block (`▀`),
+/// and the `bottom` pixel as its background. Both are blended with `bg`
+/// according to their alpha channel (see [`Pixel::on`]). A missing pixel is
+/// drawn with the `letterbox` color, and if both pixels are missing the cell
+/// is left blank with a `letterbox` background. When only the `bottom` pixel
+/// is present, a lower half block (`▄`) is used instead, so the letterbox is
+/// always drawn as a background color.
+///
+/// This is the building block used by the [`Widget`] implementation of
+/// [`ImageView`], and can be used to compose image output cell-by-cell into
+/// a custom [`Buffer`].
+pub fn render_cell(
+    top: Option<Pixel>,
+    bottom: Option<Pixel>,
+    bg: BgColor,
+    letterbox: Color,
+) -> Cell {
+    let mut cell = Cell::default();
+    match (top, bottom) {
+        (None, None) => {
+            cell.set_char(' ').set_bg(letterbox);
+            return cell;
+        }
+        (None, Some(pix)) => {
+            cell.set_char(LOWER_PIXEL_CHAR)
+                .set_fg(pix.on(bg))
+                .set_bg(letterbox);
+            return cell;
+        }
+        _ => {}
+    }
+    let top = match top {
+        None => letterbox,
+        Some(pix) => pix.on(bg),
+    };
+    let bottom = match bottom {
+        None => letterbox,
+        Some(pix) => pix.on(bg),
+    };
+    cell.set_char(PIXEL_CHAR).set_fg(top).set_bg(bottom);
+    cell
+}
+
+/// Renders sampled pixels into `cell` according to `mode`. When `clear` is
+/// `false`, letterbox is left as the existing content of the cell.
+/// Returns whether the cell was written.
+fn write_cell(
+    cell: &mut Cell,
+    mode: RenderMode,
+    samples: &[Option<Pixel>],
+    bg: BgColor,
+    luma: LumaWeights,
+    ramp: mode::Ramp,
+    clear: bool,
+) -> bool {
+    if clear {
+        *cell = mode.cell(samples, bg, Color::Reset, luma, ramp);
+    } else if samples.iter().any(Option::is_some) {
+        *cell = mode.cell(samples, bg, cell.bg, luma, ramp);
+    } else {
+        return false;
+    }
+    true
+}
+
+/// Reduces the colors of a rendered cell to the given [`ColorDepth`]
+fn reduce_colors(cell: &mut Cell, depth: ColorDepth) {
+    cell.fg = depth.reduce(cell.fg);
+    cell.bg = depth.reduce(cell.bg);
+}
+
+/// Calls `draw` with each cell of `area` that is within `buf`. Positions passed
+/// to `draw` are relative to `area`.
+///
+/// Cells are visited row by row, directly in the content of the buffer.
+fn fill_area<F>(area: Rect, buf: &mut Buffer, mut draw: F)
+where
+    F: FnMut(u16, u16, &mut Cell),
+{
+    let visible = area.intersection(buf.area);
+    if visible.is_empty() {
+        return;
+    }
+    let x_off = visible.x - area.x;
+    for y in (visible.y - area.y)..(visible.bottom() - area.y) {
+        let start = buf.index_of(visible.x, area.y + y);
+        let row = &mut buf.content[start..(start + visible.width as usize)];
+        for (x, cell) in (x_off..).zip(row.iter_mut()) {
+            draw(x, y, cell);
+        }
+    }
+}
+
+impl<'a> Widget for ImageView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.render_with_stats(area, buf);
+    }
+}
+
+impl<'a> Crossfade<'a> {
+    /// Creates a crossfade from the view `from` to the view `to`, at the blend
+    /// factor `t`.
+    ///
+    /// A factor of `0.0` renders only `from`, and `1.0` renders only `to`. The
+    /// factor is clamped to that range.
+    pub fn new(from: ImageView<'a>, to: ImageView<'a>, t: f32) -> Self {
+        Self {
+            from,
+            to,
+            t: t.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Gets the view being faded out
+    pub fn from(&self) -> &ImageView<'a> {
+        &self.from
+    }
+
+    /// Gets the view being faded in
+    pub fn to(&self) -> &ImageView<'a> {
+        &self.to
+    }
+
+    /// Gets the blend factor
+    pub fn t(&self) -> f32 {
+        self.t
+    }
+
+    fn mix(&self, from: Option<Pixel>, to: Option<Pixel>) -> Option<Pixel> {
+        match (from, to) {
+            (Some(from_pix), Some(to_pix)) => Some(
+                from_pix
+                    .over(self.from.bg)
+                    .blend(to_pix.over(self.to.bg), self.t),
+            ),
+            (Some(pix), None) if self.t < 0.5 => Some(pix.over(self.from.bg)),
+            (None, Some(pix)) if self.t >= 0.5 => Some(pix.over(self.to.bg)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Widget for Crossfade<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let from_mapping = self.from.mapping(area);
+        let to_mapping = self.to.mapping(area);
+        let clear = self.from.clear || self.to.clear;
+        fill_area(area, buf, |x, y, cell| {
+            let (from1, from2) = self.from.sample(&from_mapping, x, y);
+            let (to1, to2) = self.to.sample(&to_mapping, x, y);
+            let pix1 = self.mix(from1, to1);
+            let pix2 = self.mix(from2, to2);
+            let written = write_cell(
+                cell,
+                RenderMode::HalfBlock,
+                &[pix1, pix2],
+                BgColor::default(),
+                LumaWeights::default(),
+                mode::Ramp::new(mode::ASCII_RAMP),
+                clear,
+            );
+            if written {
+                reduce_colors(cell, to_mapping.color_depth);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds an opaque image whose pixels encode their coordinates, with `x`
+    /// in the red and `y` in the green channel
+    fn indexed(width: usize, height: usize) -> Image {
+        let data: Vec<u8> = (0..width * height)
+            .flat_map(|idx| [(idx % width) as u8, (idx / width) as u8, 0, 255])
+            .collect();
+        Image::from_rgba_bytes(width, height, &data).unwrap()
+    }
+
+    /// Hashes a value with the standard library hasher
+    fn hash_of<T: Hash>(val: &T) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        val.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn crop_to_wider_aspect() {
+        let image = indexed(4, 4).crop_to_aspect(2, 1);
+        assert_eq!((image.width, image.height), (4, 2));
+        assert_eq!(image.pixel(0, 0), indexed(4, 4).pixel(0, 1));
+        assert_eq!(image.pixel(3, 1), indexed(4, 4).pixel(3, 2));
+    }
+
+    #[test]
+    fn crop_to_taller_aspect() {
+        let image = indexed(6, 3).crop_to_aspect(1, 1);
+        assert_eq!((image.width, image.height), (3, 3));
+        assert_eq!(image.pixel(0, 0), indexed(6, 3).pixel(1, 0));
+        assert_eq!(image.pixel(2, 2), indexed(6, 3).pixel(3, 2));
+    }
+
+    #[test]
+    fn render_matches_golden_cells() {
+        let image = indexed(2, 2);
+        let view = image.view().with_color_depth(ColorDepth::TrueColor);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 3));
+        view.render(Rect::new(1, 1, 2, 1), &mut buf);
+        let mut expected = Buffer::empty(Rect::new(0, 0, 4, 3));
+        for x in 0..2 {
+            expected
+                .get_mut(1 + x, 1)
+                .set_char('▀')
+                .set_fg(Color::Rgb(x as u8, 0, 0))
+                .set_bg(Color::Rgb(x as u8, 1, 0));
+        }
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn render_matches_per_cell_writes() {
+        let image = indexed(5, 7);
+        let view = image.view().with_color_depth(ColorDepth::TrueColor);
+        // Partially outside of the buffer, so rows are clipped
+        let area = Rect::new(2, 1, 6, 4);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 5, 4));
+        view.render(area, &mut buf);
+        let mut expected = Buffer::empty(Rect::new(0, 0, 5, 4));
+        for (x, y, cell) in view.cells(area) {
+            if expected.area.contains((x, y).into()) {
+                *expected.get_mut(x, y) = cell;
+            }
+        }
+        assert_eq!(buf, expected);
+    }
+
+    /// A buffer of `width` by `height` cells all set to `x`, to detect writes
+    fn filled_buffer(width: u16, height: u16) -> Buffer {
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        buf.content.iter_mut().for_each(|cell| {
+            cell.set_char('x');
+        });
+        buf
+    }
+
+    #[test]
+    fn render_zero_width_region() {
+        let image = indexed(4, 4);
+        let view = image.view().with_region(Region {
+            x: 1,
+            y: 0,
+            width: 0,
+            height: 4,
+        });
+        let mut buf = filled_buffer(3, 2);
+        view.render(buf.area, &mut buf);
+        for cell in &buf.content {
+            assert_eq!(cell.symbol(), " ");
+            assert_eq!(cell.bg, Color::Reset);
+        }
+    }
+
+    #[test]
+    fn render_zero_size_area() {
+        let image = indexed(4, 4);
+        let mut buf = filled_buffer(3, 2);
+        image.view().render(Rect::new(1, 1, 0, 0), &mut buf);
+        image.view().render(Rect::new(1, 1, 0, 1), &mut buf);
+        image.view().render(Rect::new(1, 1, 1, 0), &mut buf);
+        assert_eq!(buf, filled_buffer(3, 2));
+    }
+
+    #[test]
+    fn eq_ignores_metadata() {
+        let loaded = Image {
+            depth: BitDepth::U16,
+            format: Some(image::ImageFormat::Png),
+            icc_profile: Some(vec![1, 2, 3]),
+            ..indexed(3, 2)
+        };
+        assert_eq!(loaded, indexed(3, 2));
+        assert_eq!(hash_of(&loaded), hash_of(&indexed(3, 2)));
+        assert_ne!(indexed(3, 2), indexed(2, 3));
+    }
+
+    #[test]
+    fn f32_to_u8_rounding() {
+        assert_eq!(f32_to_u8(0.0), 0);
+        assert_eq!(f32_to_u8(0.49 / 255.0), 0);
+        assert_eq!(f32_to_u8(0.5 / 255.0), 1);
+        assert_eq!(f32_to_u8(254.5 / 255.0), 255);
+        assert_eq!(f32_to_u8(1.0), 255);
+        assert_eq!(f32_to_u8(1.5), 255);
+        assert_eq!(f32_to_u8(-0.5), 0);
+        assert_eq!(f32_to_u8(-0.0), 0);
+    }
+
+    #[test]
+    fn u16_to_u8_rounding() {
+        assert_eq!(u16_to_u8(0), 0);
+        assert_eq!(u16_to_u8(128), 0);
+        assert_eq!(u16_to_u8(129), 1);
+        assert_eq!(u16_to_u8(257), 1);
+        assert_eq!(u16_to_u8(65535 - 129), 254);
+        assert_eq!(u16_to_u8(65535 - 128), 255);
+        assert_eq!(u16_to_u8(65535), 255);
+    }
+
+    #[test]
+    fn view_image_accessors() {
+        let image = indexed(2, 2);
+        assert!(std::ptr::eq(image.view().image(), &image));
+        assert!(image.view().try_image().is_some());
+        let raw = ImageView::from_raw(image.pixels(), 2, 2).unwrap();
+        assert!(raw.try_image().is_none());
+    }
+
+    #[test]
+    fn rect_region_round_trip() {
+        let values = [0, 1, 2, 3, 7, 10, 255, 1000, u16::MAX - 1, u16::MAX];
+        for x in values {
+            for y in values {
+                for width in values {
+                    for height in values {
+                        let area = Rect {
+                            x,
+                            y,
+                            width,
+                            height,
+                        };
+                        let region = Region::from(area);
+                        let cells = [
+                            region.cell_x(),
+                            region.cell_y(),
+                            region.cell_width(),
+                            region.cell_height(),
+                        ];
+                        let expected = [x, y, width, height].map(usize::from);
+                        assert_eq!(cells, expected, "{area:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Renders a 1x2 image of `top` over `bottom` into a single cell, with
+    /// transparency passthrough enabled
+    fn passthrough_cell(top: Pixel, bottom: Pixel) -> Cell {
+        let image = Image {
+            pixels: vec![top, bottom],
+            width: 1,
+            height: 2,
+            ..Image::default()
+        };
+        let view = image
+            .view()
+            .with_transparency_passthrough(true)
+            .with_color_depth(ColorDepth::TrueColor);
+        assert!(view.is_exact(Rect::new(0, 0, 1, 1)));
+        view.render_to_buffer(1, 1).content[0].clone()
+    }
+
+    #[test]
+    fn transparency_passthrough_cases() {
+        let red = Pixel {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        let blue = Pixel {
+            r: 0,
+            g: 0,
+            b: 255,
+            a: 255,
+        };
+        let clear = Pixel::default();
+
+        let cell = passthrough_cell(red, blue);
+        assert_eq!(cell.symbol(), "▀");
+        assert_eq!(
+            (cell.fg, cell.bg),
+            (Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255))
+        );
+
+        let cell = passthrough_cell(clear, blue);
+        assert_eq!(cell.symbol(), "▄");
+        assert_eq!((cell.fg, cell.bg), (Color::Rgb(0, 0, 255), Color::Reset));
+
+        let cell = passthrough_cell(red, clear);
+        assert_eq!(cell.symbol(), "▀");
+        assert_eq!((cell.fg, cell.bg), (Color::Rgb(255, 0, 0), Color::Reset));
+
+        let cell = passthrough_cell(clear, clear);
+        assert_eq!(cell.symbol(), " ");
+        assert_eq!(cell.bg, Color::Reset);
+    }
+
+    #[test]
+    fn upscale_samples_pixel_centers() {
+        let image = indexed(2, 2);
+        let buf = image
+            .view()
+            .with_fit(Fit::Stretch)
+            .with_color_depth(ColorDepth::TrueColor)
+            .render_to_buffer(4, 2);
+        for y in 0..2 {
+            for x in 0..4 {
+                let expected = Color::Rgb(x as u8 / 2, y as u8, 0);
+                let cell = buf.get(x, y);
+                assert_eq!((cell.fg, cell.bg), (expected, expected), "cell {x}, {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn views_compare_and_hash() {
+        let image = indexed(2, 2);
+        let view = image.view().with_subpixel_offset(0.0, 0.5);
+        let same = image.view().with_subpixel_offset(-0.0, 0.5);
+        assert_eq!(view, same);
+        assert_eq!(hash_of(&view), hash_of(&same));
+        let auto = image.view().with_auto_resample(1.5);
+        assert_eq!(auto, image.view().with_auto_resample(1.5));
+        assert_ne!(auto, image.view());
+    }
+
+    #[test]
+    fn has_alpha_opaque_and_transparent() {
+        let mut image = indexed(3, 3);
+        assert!(!image.has_alpha());
+        image.pixels_mut()[4].a = 254;
+        assert!(image.has_alpha());
+        assert!(!Image::default().has_alpha());
+    }
+
+    #[test]
+    fn has_alpha_cache_invalidation() {
+        let mut image = indexed(3, 3);
+        assert!(!image.has_alpha());
+        image.pixel_mut(1, 1).unwrap().a = 0;
+        assert!(image.has_alpha());
+
+        image.fill(Pixel {
+            r: 1,
+            g: 2,
+            b: 3,
+            a: 255,
+        });
+        assert!(!image.has_alpha());
+
+        let key = *image.pixel(0, 0).unwrap();
+        image.chroma_key(key, 0);
+        assert!(image.has_alpha());
+    }
+
+    #[test]
+    fn tone_map_non_finite() {
+        for tone_map in [ToneMap::Clamp, ToneMap::Reinhard, ToneMap::Aces] {
+            assert_eq!(tone_map.apply(f32::NAN), 0, "{tone_map:?}");
+            assert_eq!(tone_map.apply(f32::INFINITY), 255, "{tone_map:?}");
+            assert_eq!(tone_map.apply(f32::NEG_INFINITY), 0, "{tone_map:?}");
+            assert_eq!(tone_map.apply(MAX_TONE_MAP_INPUT), 255, "{tone_map:?}");
+            assert_eq!(tone_map.apply(f32::MAX), 255, "{tone_map:?}");
+            assert_eq!(tone_map.apply(-f32::MAX), 0, "{tone_map:?}");
+        }
+    }
+
+    #[test]
+    fn integer_zoom_blocks() {
+        let image = indexed(3, 2);
+        for zoom in [2u16, 3] {
+            let view = image
+                .view()
+                .with_fit(Fit::Stretch)
+                .with_color_depth(ColorDepth::TrueColor);
+            let (width, height) = (3 * zoom, zoom);
+            let mapping = view.mapping(Rect::new(0, 0, width, height));
+            assert_eq!(mapping.integer_zoom, Some((zoom as isize, zoom as isize)));
+            let buf = view.render_to_buffer(width, height);
+            let source = |x: u16, y: u16| Color::Rgb((x / zoom) as u8, (y / zoom) as u8, 0);
+            for y in 0..height {
+                for x in 0..width {
+                    let cell = buf.get(x, y);
+                    let expected = (source(x, y * 2), source(x, y * 2 + 1));
+                    assert_eq!((cell.fg, cell.bg), expected, "{zoom}x cell {x}, {y}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from_other_converts_to_rgba8() {
+        let mut buf = image::ImageBuffer::<image::LumaA<u16>, _>::new(3, 2);
+        buf.put_pixel(1, 0, image::LumaA([u16::MAX, u16::MAX]));
+        let image = Image::from_other(&image::DynamicImage::ImageLumaA16(buf));
+        assert_eq!((image.width(), image.height()), (3, 2));
+        assert_eq!(image.pixels().len(), 6);
+        let white = Pixel {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 255,
+        };
+        assert_eq!(image.pixel(1, 0), Some(&white));
+        assert_eq!(image.pixel(0, 0).map(|pix| pix.a), Some(0));
+    }
+
+    #[test]
+    fn from_bytes_rejects_bad_lengths() {
+        let err = Image::from_rgba_bytes(2, 2, &[0; 15]).unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)));
+        let err = Image::from_rgb_bytes(2, 2, &[0; 16]).unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)));
+        // `width * height * channels` overflows instead of wrapping to a
+        // length that happens to match
+        let err = Image::from_rgba_bytes(usize::MAX, 2, &[]).unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)));
+        let err = Image::from_rgb_bytes(usize::MAX / 2 + 1, 2, &[]).unwrap_err();
+        assert!(matches!(err, Error::InvalidInput(_)));
+    }
+
+    #[test]
+    fn from_bytes_round_trip() {
+        let rgba: Vec<u8> = (0..24).collect();
+        let image = Image::from_rgba_bytes(3, 2, &rgba).unwrap();
+        assert_eq!((image.width(), image.height()), (3, 2));
+        let bytes: Vec<u8> = image
+            .pixels()
+            .iter()
+            .flat_map(|pix| [pix.r, pix.g, pix.b, pix.a])
+            .collect();
+        assert_eq!(bytes, rgba);
+
+        let rgb: Vec<u8> = (0..18).collect();
+        let image = Image::from_rgb_bytes(3, 2, &rgb).unwrap();
+        let bytes: Vec<u8> = image
+            .pixels()
+            .iter()
+            .flat_map(|pix| [pix.r, pix.g, pix.b])
+            .collect();
+        assert_eq!(bytes, rgb);
+        assert!(image.pixels().iter().all(|pix| pix.a == 255));
+    }
+
+    #[test]
+    fn crop_matches_source_pixels() {
+        let image = indexed(5, 4);
+        let region = Region {
+            x: 1,
+            y: 2,
+            width: 3,
+            height: 2,
+        };
+        let cropped = image.crop(region);
+        assert_eq!((cropped.width(), cropped.height()), (3, 2));
+        for y in 0..2 {
+            for x in 0..3 {
+                assert_eq!(cropped.pixel(x, y), image.pixel(x + 1, y + 2));
+            }
+        }
+    }
+
+    #[test]
+    fn crop_out_of_bounds_is_empty() {
+        let image = indexed(5, 4);
+        for (x, y) in [(5, 0), (0, 4), (10, 10)] {
+            let cropped = image.crop(Region {
+                x,
+                y,
+                width: 2,
+                height: 2,
+            });
+            assert_eq!((cropped.width(), cropped.height()), (0, 0));
+            assert!(cropped.pixels().is_empty());
+        }
+    }
+
+    #[test]
+    fn crop_overhanging_is_clamped() {
+        let image = indexed(5, 4);
+        let cropped = image.crop(Region {
+            x: 3,
+            y: 1,
+            width: 10,
+            height: 10,
+        });
+        assert_eq!((cropped.width(), cropped.height()), (2, 3));
+        for y in 0..3 {
+            for x in 0..2 {
+                assert_eq!(cropped.pixel(x, y), image.pixel(x + 3, y + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn ascii_ramp_endpoints() {
+        let gray = |val: u8| {
+            let pix = Pixel {
+                r: val,
+                g: val,
+                b: val,
+                a: 255,
+            };
+            Image::solid(2, 2, pix)
+        };
+        let (black, white) = (gray(0), gray(255));
+        for (ramp, first, last) in [
+            ("", " ", "@"),
+            (" .:-=+*#%@", " ", "@"),
+            ("·░▒▓█", "·", "█"),
+        ] {
+            for (image, expected) in [(&black, first), (&white, last)] {
+                let buf = image
+                    .view()
+                    .with_mode(RenderMode::Ascii)
+                    .with_ascii_ramp(ramp)
+                    .render_to_buffer(2, 1);
+                for x in 0..2 {
+                    assert_eq!(buf.get(x, 0).symbol(), expected, "{ramp:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dither_spreads_gradient_over_palette() {
+        // A shallow gray gradient that falls between few palette entries
+        let data: Vec<u8> = (0..32u8)
+            .flat_map(|x| {
+                let val = 100 + x / 2;
+                [val, val, val, 255]
+            })
+            .collect();
+        let image = Image::from_rgba_bytes(32, 1, &data).unwrap();
+        let colors = |dither: bool| {
+            let buf = image
+                .view()
+                .with_fit(Fit::Stretch)
+                .with_color_depth(ColorDepth::Ansi16)
+                .with_dither(dither)
+                .render_to_buffer(32, 4);
+            let mut colors: Vec<Color> = buf
+                .content
+                .iter()
+                .flat_map(|cell| [cell.fg, cell.bg])
+                .collect();
+            colors.sort_by_key(|color| format!("{color:?}"));
+            colors.dedup();
+            colors
+        };
+        assert!(colors(false).len() <= 2);
+        assert!(colors(true).len() > colors(false).len());
+    }
+
+    #[test]
+    fn dither_composites_over_background() {
+        let translucent = Image::solid(
+            4,
+            4,
+            Pixel {
+                r: 255,
+                g: 255,
+                b: 255,
+                a: 128,
+            },
+        );
+        let bg = BgColor { r: 0, g: 0, b: 255 };
+        let blended = Image::solid(4, 4, translucent.pixels()[0].over(bg));
+        let render = |image: &Image| {
+            image
+                .view()
+                .with_bg_color(bg)
+                .with_color_depth(ColorDepth::Ansi16)
+                .with_dither(true)
+                .render_to_buffer(4, 2)
+        };
+        assert_eq!(render(&translucent), render(&blended));
+    }
+
+    #[cfg(feature = "png")]
+    #[test]
+    fn from_image_reader_enforces_allocation_limit() {
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageLuma8(image::GrayImage::new(1024, 1024))
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        png.set_position(0);
+        let reader = || image::io::Reader::with_format(png.clone(), image::ImageFormat::Png);
+
+        let mut limited = reader();
+        let mut limits = image::io::Limits::default();
+        limits.max_alloc = Some(512 * 1024);
+        limited.limits(limits);
+        let err = Image::from_image_reader(limited).unwrap_err();
+        assert!(matches!(err, Error::LimitsExceeded(_)), "{err:?}");
+
+        let image = Image::from_image_reader(reader()).unwrap();
+        assert_eq!((image.width(), image.height()), (1024, 1024));
+        let image = Image::load(png).unwrap();
+        assert_eq!((image.width(), image.height()), (1024, 1024));
+    }
+
+    #[test]
+    fn opaque_pixels_skip_blending() {
+        let backgrounds = [(0, 0, 0), (255, 255, 255), (12, 200, 99), (128, 64, 255)];
+        for (r, g, b) in backgrounds {
+            let bg = BgColor { r, g, b };
+            for val in 0..=255u8 {
+                let pix = Pixel {
+                    r: val,
+                    g: 255 - val,
+                    b: val / 2,
+                    a: 255,
+                };
+                let blended = (
+                    Pixel::blend_channel(pix.r, bg.r, 255),
+                    Pixel::blend_channel(pix.g, bg.g, 255),
+                    Pixel::blend_channel(pix.b, bg.b, 255),
+                );
+                assert_eq!(pix.on(bg), Color::Rgb(blended.0, blended.1, blended.2));
+                let over = pix.over(bg);
+                assert_eq!(
+                    (over.r, over.g, over.b, over.a),
+                    (blended.0, blended.1, blended.2, 255)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn opaque_shortcut_renders_same_cells() {
+        const CHECKERBOARD: Checkerboard = Checkerboard {
+            size: 2,
+            c1: BgColor { r: 40, g: 0, b: 90 },
+            c2: BgColor {
+                r: 200,
+                g: 150,
+                b: 10,
+            },
+        };
+        let image = indexed(6, 4);
+        // A nearly opaque black pixel blends to black over any background
+        // without a channel of 255, but disables the shortcut
+        let mut translucent = image.clone();
+        translucent.pixel_mut(0, 0).unwrap().a = 254;
+        let area = Rect::new(0, 0, 6, 2);
+        assert!(image.view().mapping(area).opaque);
+        assert!(!translucent.view().mapping(area).opaque);
+        let views: [fn(ImageView) -> ImageView; 4] = [
+            |view| view,
+            |view| {
+                view.with_bg_color(BgColor {
+                    r: 80,
+                    g: 160,
+                    b: 240,
+                })
+            },
+            |view| view.with_checkerboard_bg(CHECKERBOARD),
+            |view| view.with_transparency_passthrough(true),
+        ];
+        for (idx, configure) in views.iter().enumerate() {
+            for (width, height) in [(6, 2), (12, 4), (5, 3)] {
+                let render = |image: &Image| {
+                    configure(image.view())
+                        .with_fit(Fit::Stretch)
+                        .with_resample(Resample::Nearest)
+                        .render_to_buffer(width, height)
+                };
+                assert_eq!(
+                    render(&image),
+                    render(&translucent),
+                    "view {idx}, {width}x{height}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn sixel_cells_match_render() {
+        let image = indexed(6, 4);
+        let configure: [fn(ImageView) -> ImageView; 3] = [
+            |view| view,
+            |view| view.with_fit(Fit::Stretch),
+            |view| view.with_border(Color::Red),
+        ];
+        for configure in configure {
+            let view = configure(image.view().with_protocol(Protocol::Sixel));
+            let area = Rect::new(0, 0, 9, 5);
+            let mut buf = Buffer::empty(area);
+            let mut count = 0;
+            for (x, y, cell) in view.cells(area) {
+                *buf.get_mut(x, y) = cell;
+                count += 1;
             }
+            assert_eq!(buf, view.render_to_buffer(area.width, area.height));
+            let mut stats_buf = Buffer::empty(area);
+            assert_eq!(
+                count,
+                view.render_with_stats(area, &mut stats_buf).cells_written
+            );
+            let sixel = |cell: &&Cell| cell.symbol().starts_with("\x1bP");
+            assert_eq!(buf.content.iter().filter(sixel).count(), 1);
         }
     }
 }
@@ -0,0 +1,155 @@
+use crate::Image;
+
+/// Side length of the windows SSIM is computed over
+const SSIM_WINDOW: usize = 8;
+/// Stabilizing constants of SSIM, for 8-bit values
+const SSIM_C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+const SSIM_C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+impl Image {
+    /// Computes the peak signal-to-noise ratio (PSNR) of the image against a
+    /// reference image, in decibels.
+    ///
+    /// This is `10 * log10(255² / MSE)`, where MSE is the mean squared
+    /// difference of the RGB channels of all pixels. The alpha channel is
+    /// ignored. Higher is better: identical images yield infinity, and values
+    /// above 40 are generally hard to tell apart. [`None`] is returned if the
+    /// images have different dimensions or are empty.
+    ///
+    /// This is useful for comparing the quality of e.g. [`Resample`](crate::Resample)
+    /// filters objectively, by resizing an image down and back up.
+    pub fn psnr(&self, other: &Image) -> Option<f64> {
+        if self.width != other.width || self.height != other.height || self.pixels.is_empty() {
+            return None;
+        }
+        let d = |a: u8, b: u8| (a as f64 - b as f64).powi(2);
+        let sum = self
+            .pixels
+            .iter()
+            .zip(other.pixels.iter())
+            .map(|(a, b)| d(a.r, b.r) + d(a.g, b.g) + d(a.b, b.b))
+            .sum::<f64>();
+        let mse = sum / (self.pixels.len() * 3) as f64;
+        Some(10.0 * (255.0 * 255.0 / mse).log10())
+    }
+
+    /// Computes the structural similarity index (SSIM) of the image against a
+    /// reference image, from `-1.0` to `1.0`.
+    ///
+    /// SSIM compares the local mean, variance, and covariance of the luminance
+    /// (see [`Pixel::luminance`](crate::Pixel::luminance)) of both images,
+    /// which tracks perceived quality more closely than [`psnr`](Image::psnr).
+    /// This is a basic version, averaging SSIM over non-overlapping 8x8 pixel
+    /// windows (smaller at the right and bottom edges) without Gaussian
+    /// weighting. The alpha channel is ignored. Identical images yield `1.0`.
+    /// [`None`] is returned if the images have different dimensions or are
+    /// empty.
+    pub fn ssim(&self, other: &Image) -> Option<f64> {
+        if self.width != other.width || self.height != other.height || self.pixels.is_empty() {
+            return None;
+        }
+        let mut total = 0.0;
+        let mut windows = 0;
+        for top in (0..self.height).step_by(SSIM_WINDOW) {
+            for left in (0..self.width).step_by(SSIM_WINDOW) {
+                let bottom = (top + SSIM_WINDOW).min(self.height);
+                let right = (left + SSIM_WINDOW).min(self.width);
+                let lum = |image: &Image, x: usize, y: usize| {
+                    image.pixels[y * image.width + x].luminance() as f64
+                };
+                let count = ((bottom - top) * (right - left)) as f64;
+                let (mut sum_a, mut sum_b) = (0.0, 0.0);
+                let (mut sum_aa, mut sum_bb, mut sum_ab) = (0.0, 0.0, 0.0);
+                for y in top..bottom {
+                    for x in left..right {
+                        let (a, b) = (lum(self, x, y), lum(other, x, y));
+                        sum_a += a;
+                        sum_b += b;
+                        sum_aa += a * a;
+                        sum_bb += b * b;
+                        sum_ab += a * b;
+                    }
+                }
+                let (mean_a, mean_b) = (sum_a / count, sum_b / count);
+                let var_a = sum_aa / count - mean_a * mean_a;
+                let var_b = sum_bb / count - mean_b * mean_b;
+                let covar = sum_ab / count - mean_a * mean_b;
+                total += ((2.0 * mean_a * mean_b + SSIM_C1) * (2.0 * covar + SSIM_C2))
+                    / ((mean_a * mean_a + mean_b * mean_b + SSIM_C1) * (var_a + var_b + SSIM_C2));
+                windows += 1;
+            }
+        }
+        Some(total / windows as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Image, Pixel};
+
+    /// Builds an opaque image with a diagonal gradient, offset by `delta` in
+    /// every channel
+    fn gradient(width: usize, height: usize, delta: u8) -> Image {
+        let data: Vec<u8> = (0..width * height)
+            .flat_map(|idx| {
+                let val = ((idx % width + idx / width) * 6) as u8;
+                [val + delta, val / 2 + delta, 200 - val + delta, 255]
+            })
+            .collect();
+        Image::from_rgba_bytes(width, height, &data).unwrap()
+    }
+
+    #[test]
+    fn mismatched_or_empty() {
+        let image = gradient(10, 6, 0);
+        assert_eq!(image.psnr(&gradient(6, 10, 0)), None);
+        assert_eq!(image.ssim(&gradient(10, 5, 0)), None);
+        let empty = Image::with_size(0, 0);
+        assert_eq!(empty.psnr(&empty), None);
+        assert_eq!(empty.ssim(&empty), None);
+        let empty = Image::with_size(4, 0);
+        assert_eq!(empty.psnr(&empty), None);
+        assert_eq!(empty.ssim(&empty), None);
+    }
+
+    #[test]
+    fn identical() {
+        let image = gradient(20, 13, 0);
+        assert_eq!(image.psnr(&image.clone()), Some(f64::INFINITY));
+        assert_eq!(image.ssim(&image.clone()), Some(1.0));
+    }
+
+    #[test]
+    fn psnr_of_known_mse() {
+        // Every channel differs by 10, so the MSE is 100
+        let image = gradient(20, 13, 0);
+        let offset = gradient(20, 13, 10);
+        let expected = 10.0 * (255.0f64 * 255.0 / 100.0).log10();
+        let psnr = image.psnr(&offset).unwrap();
+        assert!((psnr - expected).abs() < 1e-9, "{psnr}");
+        assert!((psnr - 28.1308).abs() < 1e-4, "{psnr}");
+        // Alpha is ignored
+        let mut translucent = offset.clone();
+        for pix in translucent.pixels_mut() {
+            pix.a = 0;
+        }
+        assert_eq!(image.psnr(&translucent), Some(psnr));
+    }
+
+    #[test]
+    fn ssim_drops_with_distortion() {
+        let image = gradient(16, 16, 0);
+        let mut noisy = image.clone();
+        for (idx, pix) in noisy.pixels_mut().iter_mut().enumerate() {
+            if idx % 3 == 0 {
+                *pix = Pixel {
+                    r: 255 - pix.r,
+                    ..*pix
+                };
+            }
+        }
+        let ssim = image.ssim(&noisy).unwrap();
+        assert!(ssim < 0.9 && ssim > -1.0, "{ssim}");
+        assert_eq!(image.ssim(&noisy), noisy.ssim(&image));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::{render_cell, BgColor, LumaWeights, Pixel};
+use ratatui::{
+    buffer::Cell,
+    style::{Color, Modifier, Style},
+};
+
+/// The largest number of pixels sampled for a single cell by any mode
+pub(crate) const MAX_SAMPLES: usize = 8;
+
+const FULL_BLOCK_CHAR: char = '█';
+
+/// Quadrant block characters, indexed by a mask of the filled quadrants
+/// (top-left = 1, top-right = 2, bottom-left = 4, bottom-right = 8).
+const QUADRANT_CHARS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// First sextant character (U+1FB00), for the mask with only the top-left
+/// sextant filled. See [`sextant_char`].
+const SEXTANT_BASE: u32 = 0x1FB00;
+
+/// Bits of the braille dots, in row-major order of a 2x4 grid
+const BRAILLE_DOTS: [u32; 8] = [0x01, 0x08, 0x02, 0x10, 0x04, 0x20, 0x40, 0x80];
+const BRAILLE_BASE: u32 = 0x2800;
+
+/// Default characters of [`RenderMode::Ascii`], from darkest to brightest
+pub(crate) const ASCII_RAMP: &str = " .:-=+*#%@";
+
+/// Characters of a ramp from darkest to brightest, resolved once per render
+/// so cells do not have to count them
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Ramp<'a> {
+    chars: &'a str,
+    len: usize,
+}
+
+impl<'a> Ramp<'a> {
+    /// Resolves the characters of `ramp`, or of [`ASCII_RAMP`] if it is empty
+    pub(crate) fn new(ramp: &'a str) -> Self {
+        let chars = if ramp.is_empty() { ASCII_RAMP } else { ramp };
+        Self {
+            chars,
+            len: chars.chars().count(),
+        }
+    }
+
+    /// Gets the character at `idx`, which must be less than the length
+    fn get(&self, idx: usize) -> char {
+        self.chars.chars().nth(idx).unwrap_or(' ')
+    }
+}
+
+/// Rendering mode of an [`ImageView`](crate::ImageView), selecting how pixels
+/// are mapped to terminal cells.
+///
+/// Modes other than [`RenderMode::HalfBlock`] sample a grid of pixels for each
+/// cell. The [`Fit`](crate::Fit) mode and aspect ratio are respected the same
+/// way in every mode, so modes can be switched without changing the layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RenderMode {
+    /// Two vertically stacked pixels per cell, drawn with an upper half block
+    /// (`▀`) using truecolor foreground and background colors.
+    #[default]
+    HalfBlock,
+    /// One pixel per cell, drawn with a full block (`█`). This has half the
+    /// vertical resolution of [`RenderMode::HalfBlock`], but does not rely on
+    /// the font drawing half blocks seamlessly.
+    FullBlock,
+    /// A 2x2 grid of pixels per cell, drawn with quadrant block characters
+    /// (e.g. `▚`, `▙`). Each cell can only show two colors, so the pixels are
+    /// split into the two groups that best approximate the original colors.
+    Quadrant,
+    /// A 2x3 grid of pixels per cell, drawn with the sextant block characters
+    /// of Unicode 13 (U+1FB00 to U+1FB3B, e.g. `🬗`). Like
+    /// [`RenderMode::Quadrant`], each cell can only show two colors, so the
+    /// pixels are split into the two groups that best approximate the original
+    /// colors. This has 50% more vertical resolution than
+    /// [`RenderMode::HalfBlock`], but many fonts lack the sextant characters,
+    /// so the terminal may draw placeholder glyphs instead. Fall back to
+    /// [`RenderMode::Quadrant`] in that case.
+    Sextant,
+    /// A 2x4 grid of pixels per cell, drawn with braille patterns (e.g. `⣿`).
+    /// Pixels brighter than the average of the cell are drawn as dots in the
+    /// average color of the dots, over the average color of the remaining
+    /// pixels.
+    Braille,
+    /// One character per cell, chosen from a ramp of characters by the
+    /// luminance of the pixels, without any color. This works on any terminal,
+    /// at the cost of all color information. The ramp defaults to
+    /// `" .:-=+*#%@"`, and can be changed with
+    /// [`ImageView::set_ascii_ramp`](crate::ImageView::set_ascii_ramp).
+    Ascii,
+    /// Like [`RenderMode::Ascii`], but with two extra shades above the ramp for
+    /// the brightest pixels: a bold `@`, and a reversed (i.e. filled) blank
+    /// cell. Only the default colors of the terminal and text attributes are
+    /// used, so this is suited for terminals without color support, such as
+    /// serial consoles. See also [`RenderMode::from_env`].
+    Monochrome,
+}
+
+impl RenderMode {
+    /// The number of columns and rows of pixels sampled for each cell
+    pub(crate) fn grid(&self) -> (usize, usize) {
+        match self {
+            RenderMode::HalfBlock => (1, 2),
+            RenderMode::FullBlock => (1, 1),
+            RenderMode::Quadrant => (2, 2),
+            RenderMode::Sextant => (2, 3),
+            RenderMode::Braille => (2, 4),
+            RenderMode::Ascii => (1, 2),
+            RenderMode::Monochrome => (1, 2),
+        }
+    }
+
+    /// Chooses a mode from the environment: [`RenderMode::Monochrome`] if the
+    /// `NO_COLOR` environment variable is set to a non-empty value (see
+    /// <https://no-color.org>), and [`RenderMode::HalfBlock`] otherwise.
+    ///
+    /// The environment is only consulted when this is called, so the default
+    /// mode of an [`ImageView`](crate::ImageView) is not affected by it.
+    pub fn from_env() -> RenderMode {
+        match std::env::var_os("NO_COLOR") {
+            Some(val) if !val.is_empty() => RenderMode::Monochrome,
+            _ => RenderMode::HalfBlock,
+        }
+    }
+
+    /// Builds the cell for the sampled pixels, given in row-major order of the
+    /// mode's [`grid`](RenderMode::grid). Missing pixels are drawn with the
+    /// `letterbox` color. Modes based on brightness compute it with `luma`,
+    /// and the character modes choose characters from `ramp`.
+    pub(crate) fn cell(
+        &self,
+        samples: &[Option<Pixel>],
+        bg: BgColor,
+        letterbox: Color,
+        luma: LumaWeights,
+        ramp: Ramp,
+    ) -> Cell {
+        match self {
+            RenderMode::HalfBlock => render_cell(samples[0], samples[1], bg, letterbox),
+            RenderMode::FullBlock => full_block_cell(samples[0], bg, letterbox),
+            RenderMode::Quadrant => quadrant_cell(samples, bg, letterbox),
+            RenderMode::Sextant => sextant_cell(samples, bg, letterbox),
+            RenderMode::Braille => braille_cell(samples, bg, letterbox, luma),
+            RenderMode::Ascii => ascii_cell(samples, bg, letterbox, luma, ramp),
+            RenderMode::Monochrome => monochrome_cell(samples, bg, letterbox, luma, ramp),
+        }
+    }
+}
+
+fn rgb(pix: Pixel) -> Color {
+    Color::Rgb(pix.r, pix.g, pix.b)
+}
+
+/// Averages the colors of the pixels selected by `mask`
+fn average(colors: &[Pixel], mask: u32) -> Pixel {
+    let mut sum = [0u32; 3];
+    let mut count = 0;
+    for (idx, pix) in colors.iter().enumerate() {
+        if mask & (1 << idx) != 0 {
+            sum[0] += pix.r as u32;
+            sum[1] += pix.g as u32;
+            sum[2] += pix.b as u32;
+            count += 1;
+        }
+    }
+    let avg = |sum: u32| ((sum + count / 2) / count.max(1)) as u8;
+    Pixel {
+        r: avg(sum[0]),
+        g: avg(sum[1]),
+        b: avg(sum[2]),
+        a: 255,
+    }
+}
+
+fn distance(a: Pixel, b: Pixel) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(a.r, b.r) + d(a.g, b.g) + d(a.b, b.b)
+}
+
+/// Blends the present samples with `bg`, returning them along with a mask of
+/// which samples are present.
+fn resolve(samples: &[Option<Pixel>], bg: BgColor) -> ([Pixel; MAX_SAMPLES], u32) {
+    let mut colors = [Pixel::default(); MAX_SAMPLES];
+    let mut present = 0;
+    for (idx, pix) in samples.iter().enumerate() {
+        if let Some(pix) = pix {
+            colors[idx] = pix.over(bg);
+            present |= 1 << idx;
+        }
+    }
+    (colors, present)
+}
+
+/// Splits the samples into two groups of colors, returning the mask of the
+/// foreground group and the foreground and background colors. Missing samples
+/// are always in the background group, which is then the `letterbox` color.
+///
+/// When all samples are present, every split is tried, and the one with the
+/// least squared error between the original colors and the group colors is
+/// chosen.
+pub(crate) fn two_color_split(
+    samples: &[Option<Pixel>],
+    bg: BgColor,
+    letterbox: Color,
+) -> Option<(u32, Color, Color)> {
+    let (colors, present) = resolve(samples, bg);
+    let colors = &colors[..samples.len()];
+    let all = (1u32 << samples.len()) - 1;
+    if present == 0 {
+        return None;
+    }
+    if present != all {
+        return Some((present, rgb(average(colors, present)), letterbox));
+    }
+
+    let mut best = (all, average(colors, all), Pixel::default());
+    let mut best_err = colors.iter().map(|pix| distance(*pix, best.1)).sum::<u32>();
+    // A mask and its complement produce the same split, so only masks with the
+    // highest sample in the foreground need to be tried.
+    let high = 1 << (samples.len() - 1);
+    for mask in high..all {
+        let fg = average(colors, mask);
+        let bg = average(colors, all & !mask);
+        let err = colors
+            .iter()
+            .enumerate()
+            .map(|(idx, pix)| distance(*pix, if mask & (1 << idx) != 0 { fg } else { bg }))
+            .sum::<u32>();
+        if err < best_err {
+            best = (mask, fg, bg);
+            best_err = err;
+        }
+    }
+    Some((best.0, rgb(best.1), rgb(best.2)))
+}
+
+fn blank_cell(letterbox: Color) -> Cell {
+    let mut cell = Cell::default();
+    cell.set_char(' ').set_bg(letterbox);
+    cell
+}
+
+fn full_block_cell(pix: Option<Pixel>, bg: BgColor, letterbox: Color) -> Cell {
+    let Some(pix) = pix else {
+        return blank_cell(letterbox);
+    };
+    let color = pix.on(bg);
+    let mut cell = Cell::default();
+    cell.set_char(FULL_BLOCK_CHAR).set_fg(color).set_bg(color);
+    cell
+}
+
+fn quadrant_cell(samples: &[Option<Pixel>], bg: BgColor, letterbox: Color) -> Cell {
+    let Some((mask, fg, bg)) = two_color_split(samples, bg, letterbox) else {
+        return blank_cell(letterbox);
+    };
+    let mut cell = Cell::default();
+    cell.set_char(QUADRANT_CHARS[mask as usize])
+        .set_fg(fg)
+        .set_bg(bg);
+    cell
+}
+
+/// Gets the sextant block character for a mask of the filled sextants, with
+/// bits in row-major order (top-left = 1, top-right = 2, ..., bottom-right =
+/// 32).
+///
+/// The sextant characters skip the masks that already have a block character:
+/// empty, left half, right half, and full.
+fn sextant_char(mask: u32) -> char {
+    match mask {
+        0 => ' ',
+        0b010101 => '▌',
+        0b101010 => '▐',
+        0b111111 => FULL_BLOCK_CHAR,
+        _ => {
+            let skipped = (mask > 0b010101) as u32 + (mask > 0b101010) as u32;
+            char::from_u32(SEXTANT_BASE + mask - 1 - skipped).unwrap_or(' ')
+        }
+    }
+}
+
+fn sextant_cell(samples: &[Option<Pixel>], bg: BgColor, letterbox: Color) -> Cell {
+    let Some((mask, fg, bg)) = two_color_split(samples, bg, letterbox) else {
+        return blank_cell(letterbox);
+    };
+    let mut cell = Cell::default();
+    cell.set_char(sextant_char(mask)).set_fg(fg).set_bg(bg);
+    cell
+}
+
+fn braille_cell(
+    samples: &[Option<Pixel>],
+    bg: BgColor,
+    letterbox: Color,
+    luma: LumaWeights,
+) -> Cell {
+    let (colors, present) = resolve(samples, bg);
+    if present == 0 {
+        return blank_cell(letterbox);
+    }
+    // When some pixels are missing, the background is letterbox, so every
+    // present pixel is drawn as a dot.
+    let partial = present.count_ones() < samples.len() as u32;
+    let lum = |idx: usize| colors[idx].luminance_with(luma) as u32;
+    let mean = (0..samples.len()).map(lum).sum::<u32>() / samples.len() as u32;
+    let mut dots = 0;
+    for idx in 0..samples.len() {
+        if present & (1 << idx) != 0 && (partial || lum(idx) > mean) {
+            dots |= 1 << idx;
+        }
+    }
+    let rest = present & !dots;
+    let mut glyph = BRAILLE_BASE;
+    for (idx, bit) in BRAILLE_DOTS.iter().enumerate() {
+        if dots & (1 << idx) != 0 {
+            glyph |= bit;
+        }
+    }
+    let fg = if dots != 0 {
+        rgb(average(&colors, dots))
+    } else {
+        Color::Reset
+    };
+    let bg = if partial {
+        letterbox
+    } else if rest != 0 {
+        rgb(average(&colors, rest))
+    } else {
+        fg
+    };
+    let mut cell = Cell::default();
+    cell.set_char(char::from_u32(glyph).unwrap_or(' '))
+        .set_fg(fg)
+        .set_bg(bg);
+    cell
+}
+
+/// Averages the luminance of the present samples, or [`None`] if no sample
+/// is present
+fn mean_luminance(samples: &[Option<Pixel>], bg: BgColor, luma: LumaWeights) -> Option<usize> {
+    let (colors, present) = resolve(samples, bg);
+    if present == 0 {
+        return None;
+    }
+    let sum = (0..samples.len())
+        .filter(|idx| present & (1 << idx) != 0)
+        .map(|idx| colors[idx].luminance_with(luma) as usize)
+        .sum::<usize>();
+    Some(sum / present.count_ones() as usize)
+}
+
+fn ascii_cell(
+    samples: &[Option<Pixel>],
+    bg: BgColor,
+    letterbox: Color,
+    luma: LumaWeights,
+    ramp: Ramp,
+) -> Cell {
+    let Some(lum) = mean_luminance(samples, bg, luma) else {
+        return blank_cell(letterbox);
+    };
+    let idx = (lum * (ramp.len - 1) + 127) / 255;
+    let mut cell = Cell::default();
+    cell.set_char(ramp.get(idx));
+    cell
+}
+
+fn monochrome_cell(
+    samples: &[Option<Pixel>],
+    bg: BgColor,
+    letterbox: Color,
+    luma: LumaWeights,
+    ramp: Ramp,
+) -> Cell {
+    let Some(lum) = mean_luminance(samples, bg, luma) else {
+        return blank_cell(letterbox);
+    };
+    // The ramp is followed by a bold `@` and a reversed blank cell
+    let levels = ramp.len + 2;
+    let idx = (lum * (levels - 1) + 127) / 255;
+    let mut cell = Cell::default();
+    if idx < ramp.len {
+        cell.set_char(ramp.get(idx));
+    } else if idx == ramp.len {
+        cell.set_char('@')
+            .set_style(Style::default().add_modifier(Modifier::BOLD));
+    } else {
+        cell.set_char(' ')
+            .set_style(Style::default().add_modifier(Modifier::REVERSED));
+    }
+    cell
+}
@@ -0,0 +1,108 @@
+use crate::{Image, ImageView, Pixel, Region};
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+/// A view of an [`Image`] owned by the view itself, rather than borrowed like
+/// [`ImageView`].
+///
+/// Owning the image allows editing it through the view, in the coordinate
+/// space of the view's [`Region`] (see [`pixel_mut`](OwnedImageView::pixel_mut)),
+/// such as for an interactive editor painting on the image it displays. Render
+/// options other than the region are set on the borrowed [`ImageView`]
+/// returned by [`view`](OwnedImageView::view), which is also what the
+/// [`Widget`] implementation renders.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct OwnedImageView {
+    image: Image,
+    region: Region,
+}
+
+impl OwnedImageView {
+    /// Creates a view of the entire image, taking ownership of it
+    pub fn new(image: Image) -> Self {
+        let region = Region {
+            x: 0,
+            y: 0,
+            width: image.width(),
+            height: image.height(),
+        };
+        Self { image, region }
+    }
+
+    /// Factory pattern setter for the [`Region`] of the view
+    #[must_use = "method returns the modified value"]
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.set_region(region);
+        self
+    }
+
+    /// Setter for the [`Region`] of the view. The region is clamped to the
+    /// bounds of the image.
+    pub fn set_region(&mut self, region: Region) {
+        self.region = region.clamped(self.image.width(), self.image.height());
+    }
+
+    /// Gets the current [`Region`] of the view
+    pub fn region(&self) -> Region {
+        // The image may have been replaced through `image_mut`
+        self.region.clamped(self.image.width(), self.image.height())
+    }
+
+    /// Gets the image
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Gets the image for modification. If the image is replaced with one of
+    /// a different size, the region is clamped to its bounds.
+    pub fn image_mut(&mut self) -> &mut Image {
+        &mut self.image
+    }
+
+    /// Consumes the view, returning the image
+    pub fn into_image(self) -> Image {
+        self.image
+    }
+
+    /// Returns a borrowed [`ImageView`] of the region of the image, for
+    /// setting other render options
+    pub fn view(&self) -> ImageView<'_> {
+        self.image.view().with_region(self.region)
+    }
+
+    /// Gets the pixel value at given pixel coordinates, relative to the
+    /// [`Region`] of the view. [`None`](std::option::Option) is returned if
+    /// the coordinates are out of bounds of the region.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<&Pixel> {
+        let region = self.region();
+        if x >= region.width || y >= region.height {
+            None
+        } else {
+            self.image.pixel(x + region.x, y + region.y)
+        }
+    }
+
+    /// Gets the pixel value at given pixel coordinates, relative to the
+    /// [`Region`] of the view, for modification.
+    /// [`None`](std::option::Option) is returned if the coordinates are out
+    /// of bounds of the region.
+    pub fn pixel_mut(&mut self, x: usize, y: usize) -> Option<&mut Pixel> {
+        let region = self.region();
+        if x >= region.width || y >= region.height {
+            None
+        } else {
+            self.image.pixel_mut(x + region.x, y + region.y)
+        }
+    }
+}
+
+impl From<Image> for OwnedImageView {
+    fn from(image: Image) -> Self {
+        Self::new(image)
+    }
+}
+
+impl Widget for &OwnedImageView {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.view().render(area, buf);
+    }
+}
@@ -0,0 +1,422 @@
+use crate::{AlphaCache, Image, Pixel};
+use ratatui::layout::Rect;
+
+/// Filter used to sample the pixels of an image when it is scaled, either
+/// when rendering an [`ImageView`](crate::ImageView) or with
+/// [`Image::resize_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Resample {
+    /// Each output pixel takes the color of the single image pixel under its
+    /// center.
+    /// This is the fastest filter, and looks fine when upscaling, but drops
+    /// detail and causes aliasing when downscaling.
+    #[default]
+    Nearest,
+    /// Each output pixel takes the average color of all image pixels it
+    /// covers (area averaging). This avoids aliasing when downscaling, at the
+    /// cost of visiting every covered image pixel.
+    Average,
+    /// Each output pixel is linearly interpolated from the four image pixels
+    /// nearest to its center. This gives smooth gradients when upscaling, but
+    /// still aliases when downscaling by more than 2x.
+    Bilinear,
+    /// Each output pixel is computed with a Lanczos (windowed sinc) kernel
+    /// with `a` lobes (typically 3), stretched over the covered image pixels
+    /// when downscaling. This gives the sharpest result with the least
+    /// aliasing, but is by far the slowest filter, so it is best suited for
+    /// resizing ahead of time rather than for every rendered frame. An `a` of
+    /// `0` is treated as `1`.
+    Lanczos {
+        /// The number of lobes of the kernel, on each side of its center
+        a: u8,
+    },
+}
+
+impl Resample {
+    /// The default crossover factor for
+    /// [`ImageView::set_auto_resample`](crate::ImageView::set_auto_resample)
+    pub const DEFAULT_CROSSOVER: f32 = 1.5;
+
+    /// The Lanczos filter with 3 lobes, the usual choice for high quality
+    /// resizing
+    pub const LANCZOS3: Resample = Resample::Lanczos { a: 3 };
+
+    /// Samples a `width` by `height` image, whose pixels are given by `src`,
+    /// for an output pixel covering the rectangle from `x0`, `y0` to `x1`, `y1`
+    /// in image pixel coordinates. The center of the rectangle must be within
+    /// the image, except for filters other than [`Resample::Nearest`], which
+    /// repeat the edge pixels of the image.
+    pub(crate) fn sample<F>(
+        self,
+        src: F,
+        width: usize,
+        height: usize,
+        (x0, y0, x1, y1): (f32, f32, f32, f32),
+    ) -> Pixel
+    where
+        F: Fn(usize, usize) -> Pixel,
+    {
+        match self {
+            Resample::Nearest => src(((x0 + x1) / 2.0) as usize, ((y0 + y1) / 2.0) as usize),
+            Resample::Average => average(src, width, height, (x0, y0, x1, y1)),
+            Resample::Bilinear => bilinear(src, width, height, (x0 + x1) / 2.0, (y0 + y1) / 2.0),
+            Resample::Lanczos { a } => {
+                let a = a.max(1) as f32;
+                let x = Kernel::lanczos(a, (x0 + x1) / 2.0, x1 - x0, width);
+                let y = Kernel::lanczos(a, (y0 + y1) / 2.0, y1 - y0, height);
+                convolve(src, &x, &y)
+            }
+        }
+    }
+}
+
+/// Accumulates weighted pixels with premultiplied alpha, so the colors of
+/// transparent pixels do not bleed into the result
+#[derive(Debug, Default)]
+struct Accumulator {
+    color: [f32; 3],
+    alpha: f32,
+    weight: f32,
+}
+
+impl Accumulator {
+    fn add(&mut self, pix: Pixel, weight: f32) {
+        let alpha = pix.a as f32 * weight;
+        self.color[0] += pix.r as f32 * alpha;
+        self.color[1] += pix.g as f32 * alpha;
+        self.color[2] += pix.b as f32 * alpha;
+        self.alpha += alpha;
+        self.weight += weight;
+    }
+
+    /// Computes the weighted average. If the result is fully transparent, its
+    /// color is black.
+    fn finish(&self) -> Pixel {
+        let channel = |val: f32| val.round().clamp(0.0, 255.0) as u8;
+        let color = |sum: f32| {
+            if self.alpha > 0.0 {
+                channel(sum / self.alpha)
+            } else {
+                0
+            }
+        };
+        Pixel {
+            r: color(self.color[0]),
+            g: color(self.color[1]),
+            b: color(self.color[2]),
+            a: channel(self.alpha / self.weight),
+        }
+    }
+}
+
+/// Averages the pixels covered by the rectangle from `x0`, `y0` to `x1`, `y1`.
+/// At least one pixel is always covered.
+fn average<F>(src: F, width: usize, height: usize, (x0, y0, x1, y1): (f32, f32, f32, f32)) -> Pixel
+where
+    F: Fn(usize, usize) -> Pixel,
+{
+    let left = (x0 as usize).min(width - 1);
+    let top = (y0 as usize).min(height - 1);
+    let right = (x1.ceil() as usize).clamp(left + 1, width);
+    let bottom = (y1.ceil() as usize).clamp(top + 1, height);
+    let mut acc = Accumulator::default();
+    for y in top..bottom {
+        for x in left..right {
+            acc.add(src(x, y), 1.0);
+        }
+    }
+    acc.finish()
+}
+
+/// Interpolates the pixels at the fractional position `x`, `y`, where pixel
+/// centers are at half-integer positions
+fn bilinear<F>(src: F, width: usize, height: usize, x: f32, y: f32) -> Pixel
+where
+    F: Fn(usize, usize) -> Pixel,
+{
+    let x = (x - 0.5).clamp(0.0, (width - 1) as f32);
+    let y = (y - 0.5).clamp(0.0, (height - 1) as f32);
+    let (left, top) = (x as usize, y as usize);
+    let right = (left + 1).min(width - 1);
+    let bottom = (top + 1).min(height - 1);
+    let (tx, ty) = (x - left as f32, y - top as f32);
+    let mut acc = Accumulator::default();
+    acc.add(src(left, top), (1.0 - tx) * (1.0 - ty));
+    acc.add(src(right, top), tx * (1.0 - ty));
+    acc.add(src(left, bottom), (1.0 - tx) * ty);
+    acc.add(src(right, bottom), tx * ty);
+    acc.finish()
+}
+
+/// Weights of a separable kernel along one axis
+struct Kernel {
+    /// The (clamped) pixel index and weight of each tap
+    taps: Vec<(usize, f32)>,
+}
+
+impl Kernel {
+    /// Builds a Lanczos kernel with `a` lobes centered at `center` along an
+    /// axis of `len` pixels, for output pixels `scale` image pixels wide.
+    /// Taps beyond the edges are clamped to the edge pixels.
+    fn lanczos(a: f32, center: f32, scale: f32, len: usize) -> Kernel {
+        // When downscaling, the kernel is stretched to cover every image pixel
+        // of the output pixel, which low-pass filters the image.
+        let stretch = scale.max(1.0);
+        let support = a * stretch;
+        let first = (center - 0.5 - support).floor() as isize + 1;
+        let last = (center - 0.5 + support).ceil() as isize;
+        let mut taps = Vec::with_capacity((last - first + 1).max(0) as usize);
+        for idx in first..=last {
+            let dist = (idx as f32 + 0.5 - center) / stretch;
+            let weight = lanczos(dist, a);
+            if weight != 0.0 {
+                taps.push((idx.clamp(0, len as isize - 1) as usize, weight));
+            }
+        }
+        // The tap nearest to the center is always within the first lobe, so
+        // the total is never zero.
+        let total = taps.iter().map(|(_, weight)| weight).sum::<f32>();
+        for (_, weight) in taps.iter_mut() {
+            *weight /= total;
+        }
+        Kernel { taps }
+    }
+}
+
+/// The Lanczos kernel with `a` lobes
+fn lanczos(x: f32, a: f32) -> f32 {
+    if x == 0.0 {
+        1.0
+    } else if x.abs() >= a {
+        0.0
+    } else {
+        let px = std::f32::consts::PI * x;
+        a * px.sin() * (px / a).sin() / (px * px)
+    }
+}
+
+/// Applies the separable kernels `kx` and `ky` to the pixels given by `src`
+fn convolve<F>(src: F, kx: &Kernel, ky: &Kernel) -> Pixel
+where
+    F: Fn(usize, usize) -> Pixel,
+{
+    let mut acc = Accumulator::default();
+    for &(y, wy) in ky.taps.iter() {
+        for &(x, wx) in kx.taps.iter() {
+            acc.add(src(x, y), wx * wy);
+        }
+    }
+    acc.finish()
+}
+
+impl Image {
+    /// Resizes the image to `width` by `height` pixels with nearest-neighbor
+    /// sampling, returning the result as a new image.
+    ///
+    /// This is [`resize_with`](Image::resize_with) with [`Resample::Nearest`],
+    /// which is fast and keeps pixels crisp when upscaling, but drops detail
+    /// when downscaling. Alpha is preserved, and a `width` or `height` of `0`
+    /// yields an empty image.
+    pub fn resize(&self, width: usize, height: usize) -> Image {
+        self.resize_with(width, height, Resample::Nearest)
+    }
+
+    /// Resizes the image to `width` by `height` pixels with the given
+    /// [`Resample`] filter, returning the result as a new image.
+    ///
+    /// The image is stretched to the new size, so the aspect ratio is only
+    /// preserved if the new size has the same ratio. If the image is empty,
+    /// the result is filled with fully transparent pixels.
+    ///
+    /// Filters combining several pixels weight their colors by alpha
+    /// (premultiplied alpha), so fully transparent pixels do not leave a halo
+    /// of their color around opaque content.
+    ///
+    /// From fastest to highest quality: [`Resample::Nearest`] is blocky but
+    /// crisp, [`Resample::Bilinear`] is smooth when upscaling,
+    /// [`Resample::Average`] avoids dropping detail when downscaling, and
+    /// [`Resample::LANCZOS3`] is the sharpest in both directions. For
+    /// thumbnails created ahead of time, the cost of Lanczos is rarely a
+    /// concern.
+    pub fn resize_with(&self, width: usize, height: usize, filter: Resample) -> Image {
+        if self.width == 0 || self.height == 0 {
+            return Image {
+                pixels: vec![Pixel::default(); width * height],
+                width,
+                height,
+                depth: self.depth,
+                format: self.format,
+                icc_profile: self.icc_profile.clone(),
+                alpha: AlphaCache::default(),
+            };
+        }
+        let scale_x = self.width as f32 / width as f32;
+        let scale_y = self.height as f32 / height as f32;
+        let src = |x: usize, y: usize| self.pixels[y * self.width + x];
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let area = (
+                    x as f32 * scale_x,
+                    y as f32 * scale_y,
+                    (x + 1) as f32 * scale_x,
+                    (y + 1) as f32 * scale_y,
+                );
+                pixels.push(filter.sample(src, self.width, self.height, area));
+            }
+        }
+        Image {
+            pixels,
+            width,
+            height,
+            depth: self.depth,
+            format: self.format,
+            icc_profile: self.icc_profile.clone(),
+            alpha: AlphaCache::default(),
+        }
+    }
+
+    /// Resizes the image with the given [`Resample`] filter to exactly the
+    /// pixel size of `area` when rendered: one pixel per column and two pixels
+    /// per row of cells.
+    ///
+    /// Filters such as [`blur`](Image::blur) and [`sharpen`](Image::sharpen)
+    /// take time proportional to the number of pixels, so running them on the
+    /// result instead of a large source image is much faster. An
+    /// [`ImageView`](crate::ImageView) of the result takes the exact-size path
+    /// when rendered into `area`. Like [`resize_with`](Image::resize_with), the
+    /// image is stretched, so the aspect ratio is only preserved if `area` has
+    /// the same ratio.
+    pub fn prepare_for(&self, area: Rect, filter: Resample) -> Image {
+        self.resize_with(area.width as usize, area.height as usize * 2, filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a single row of gray pixels following a sine wave of the given
+    /// period, in pixels, around mid gray
+    fn wave(width: usize, period: f32) -> Image {
+        let data: Vec<u8> = (0..width)
+            .flat_map(|x| {
+                let phase = std::f32::consts::TAU * (x as f32 + 0.5) / period;
+                let val = (128.0 + 100.0 * phase.sin()).round() as u8;
+                [val, val, val, 255]
+            })
+            .collect();
+        Image::from_rgba_bytes(width, 1, &data).unwrap()
+    }
+
+    /// Largest deviation of the red channel from mid gray
+    fn deviation(image: &Image) -> u8 {
+        image
+            .pixels()
+            .iter()
+            .map(|pix| pix.r.abs_diff(128))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn lanczos_aliases_less_than_average() {
+        // Detail finer than the output pixels should average out to gray
+        for period in [2.5, 3.0] {
+            let average = wave(96, period).resize_with(24, 1, Resample::Average);
+            let lanczos = wave(96, period).resize_with(24, 1, Resample::LANCZOS3);
+            assert!(deviation(&lanczos) < deviation(&average));
+        }
+        // Coarse detail should be kept
+        let lanczos = wave(96, 64.0).resize_with(24, 1, Resample::LANCZOS3);
+        assert!(deviation(&lanczos) >= deviation(&wave(96, 64.0)) - 2);
+    }
+
+    #[test]
+    fn resize_without_transparent_halo() {
+        let red = Pixel {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        let clear = Pixel {
+            r: 0,
+            g: 255,
+            b: 0,
+            a: 0,
+        };
+        let mut image = Image::solid(8, 8, red);
+        for (idx, pix) in image.pixels_mut().iter_mut().enumerate() {
+            if idx % 8 >= 4 {
+                *pix = clear;
+            }
+        }
+        for filter in [Resample::Average, Resample::Bilinear, Resample::LANCZOS3] {
+            for (width, height) in [(3, 3), (5, 2), (16, 16)] {
+                let resized = image.resize_with(width, height, filter);
+                for pix in resized.pixels().iter().filter(|pix| pix.a > 0) {
+                    assert_eq!((pix.r, pix.g, pix.b), (255, 0, 0), "{filter:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn resize_nearest_upscales_blocks() {
+        let black = Pixel {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        };
+        let white = Pixel {
+            r: 255,
+            g: 255,
+            b: 255,
+            a: 255,
+        };
+        let mut image = Image::solid(2, 2, black);
+        *image.pixel_mut(1, 0).unwrap() = white;
+        *image.pixel_mut(0, 1).unwrap() = white;
+        let resized = image.resize(4, 4);
+        assert_eq!((resized.width(), resized.height()), (4, 4));
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(resized.pixel(x, y), image.pixel(x / 2, y / 2), "{x}, {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn resize_to_zero_is_empty() {
+        let image = wave(8, 4.0);
+        for (width, height) in [(0, 3), (3, 0), (0, 0)] {
+            let resized = image.resize(width, height);
+            assert_eq!((resized.width(), resized.height()), (width, height));
+            assert!(resized.pixels().is_empty());
+        }
+    }
+
+    #[test]
+    fn bilinear_downscale_keeps_gradient_monotonic() {
+        let data: Vec<u8> = (0..64u8)
+            .flat_map(|x| {
+                let val = x * 4;
+                [val, val, val, 255]
+            })
+            .collect();
+        let image = Image::from_rgba_bytes(64, 1, &data).unwrap();
+        for width in [5, 16, 31] {
+            let resized = image.resize_with(width, 1, Resample::Bilinear);
+            assert!(
+                resized
+                    .pixels()
+                    .windows(2)
+                    .all(|pair| pair[0].r <= pair[1].r),
+                "{width}"
+            );
+            assert!(resized.pixels()[0].r < resized.pixels()[width - 1].r);
+        }
+    }
+}
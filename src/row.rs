@@ -0,0 +1,74 @@
+use crate::ImageView;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    widgets::Widget,
+};
+
+/// A widget rendering several [`ImageView`]s side by side, such as for a
+/// gallery or a before and after comparison.
+///
+/// The width of the area is divided between the views, evenly by default or
+/// according to weights (see [`with_weights`](ImageRow::with_weights)), and
+/// each view is rendered into its slot with its own settings, exactly as if it
+/// were rendered alone. Every slot spans the full height of the area, so
+/// images line up on the same cell rows.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageRow<'a> {
+    views: &'a [ImageView<'a>],
+    weights: &'a [u16],
+    spacing: u16,
+}
+
+impl<'a> ImageRow<'a> {
+    /// Creates a row of the views, from left to right, with the width divided
+    /// evenly and no spacing
+    pub fn new(views: &'a [ImageView<'a>]) -> Self {
+        Self {
+            views,
+            weights: &[],
+            spacing: 0,
+        }
+    }
+
+    /// Factory pattern setter for the weights of the views, dividing the width
+    /// proportionally. Views without a weight (if there are fewer weights than
+    /// views) have a weight of 1.
+    #[must_use = "method returns the modified value"]
+    pub fn with_weights(mut self, weights: &'a [u16]) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Factory pattern setter for the number of empty columns between views.
+    /// Defaults to 0.
+    #[must_use = "method returns the modified value"]
+    pub fn with_spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Gets the views of the row
+    pub fn views(&self) -> &'a [ImageView<'a>] {
+        self.views
+    }
+
+    /// Computes the area each view is rendered into, when rendering the row
+    /// into `area`
+    pub fn slots(&self, area: Rect) -> Vec<Rect> {
+        let constraints = (0..self.views.len())
+            .map(|idx| Constraint::Fill(self.weights.get(idx).copied().unwrap_or(1)));
+        Layout::horizontal(constraints)
+            .spacing(self.spacing)
+            .split(area)
+            .to_vec()
+    }
+}
+
+impl<'a> Widget for ImageRow<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for (view, slot) in self.views.iter().zip(self.slots(area)) {
+            view.render(slot, buf);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{Image, Pixel};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The largest number of palette colors of an encoded image, which is the
+/// number of color registers supported by most terminals
+const MAX_COLORS: usize = 256;
+/// Runs of a sixel at least this long are run-length encoded
+const MIN_RUN: usize = 4;
+
+/// Encodes `width` by `height` pixels, in row-major order, as a Sixel escape
+/// sequence. Missing pixels are left transparent. The colors are quantized to
+/// a palette of at most [`MAX_COLORS`] colors.
+pub(crate) fn encode(pixels: &[Option<Pixel>], width: usize, height: usize) -> String {
+    let present: Vec<Pixel> = pixels.iter().flatten().copied().collect();
+    let present_len = present.len();
+    let (quantized, palette) = Image {
+        pixels: present,
+        width: present_len,
+        height: 1,
+        ..Image::default()
+    }
+    .quantize_with_palette(MAX_COLORS);
+    let lookup: HashMap<[u8; 3], usize> = palette
+        .iter()
+        .enumerate()
+        .map(|(idx, pix)| ([pix.r, pix.g, pix.b], idx))
+        .collect();
+    let mut quantized = quantized.pixels.iter();
+    let indices: Vec<Option<usize>> = pixels
+        .iter()
+        .map(|pix| {
+            pix.and_then(|_| quantized.next())
+                .map(|pix| lookup[&[pix.r, pix.g, pix.b]])
+        })
+        .collect();
+
+    // Pixel aspect ratio 1:1, with a transparent background
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", width, height);
+    let percent = |val: u8| (val as u32 * 100 + 127) / 255;
+    for (idx, pix) in palette.iter().enumerate() {
+        let _ = write!(
+            out,
+            "#{};2;{};{};{}",
+            idx,
+            percent(pix.r),
+            percent(pix.g),
+            percent(pix.b)
+        );
+    }
+
+    let mut bands: Vec<Option<Vec<u8>>> = vec![None; palette.len()];
+    for band_top in (0..height).step_by(6) {
+        for row in 0..6.min(height - band_top) {
+            let y = band_top + row;
+            for x in 0..width {
+                if let Some(color) = indices[y * width + x] {
+                    let bits = bands[color].get_or_insert_with(|| vec![0; width]);
+                    bits[x] |= 1 << row;
+                }
+            }
+        }
+        let mut first = true;
+        for (color, bits) in bands.iter_mut().enumerate() {
+            let Some(bits) = bits.take() else {
+                continue;
+            };
+            if !first {
+                // Return to the start of the band for the next color
+                out.push('$');
+            }
+            first = false;
+            let _ = write!(out, "#{}", color);
+            write_runs(&mut out, &bits);
+        }
+        if band_top + 6 < height {
+            out.push('-');
+        }
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Writes the sixels of a color in a band, run-length encoded. Trailing empty
+/// sixels are omitted.
+fn write_runs(out: &mut String, bits: &[u8]) {
+    let len = bits
+        .iter()
+        .rposition(|val| *val != 0)
+        .map_or(0, |idx| idx + 1);
+    let mut idx = 0;
+    while idx < len {
+        let run = bits[idx..len]
+            .iter()
+            .take_while(|val| **val == bits[idx])
+            .count();
+        let ch = (b'?' + bits[idx]) as char;
+        if run >= MIN_RUN {
+            let _ = write!(out, "!{}{}", run, ch);
+        } else {
+            (0..run).for_each(|_| out.push(ch));
+        }
+        idx += run;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Pixel = Pixel {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+    const BLUE: Pixel = Pixel {
+        r: 0,
+        g: 0,
+        b: 255,
+        a: 255,
+    };
+
+    /// Splits an encoded image into its raster attributes, the color
+    /// registers by RGB percentages, and the sixel data
+    fn split(out: &str) -> (&str, HashMap<[u32; 3], usize>, &str) {
+        let body = out.strip_prefix("\x1bP0;1;0q").unwrap();
+        let body = body.strip_suffix("\x1b\\").unwrap();
+        let end = body.find('#').unwrap_or(body.len());
+        let (attrs, mut data) = body.split_at(end);
+        let mut registers = HashMap::new();
+        while let Some(rest) = data.strip_prefix('#') {
+            let end = rest.find('#').unwrap_or(rest.len());
+            let params: Vec<&str> = rest[..end].split(';').collect();
+            let [idx, "2", r, g, b] = params[..] else {
+                break;
+            };
+            let rgb = [r, g, b].map(|val| val.parse().unwrap());
+            registers.insert(rgb, idx.parse().unwrap());
+            data = &rest[end..];
+        }
+        (attrs, registers, data)
+    }
+
+    #[test]
+    fn encode_bands() {
+        // Two bands, with a single pixel in the second one
+        let mut pixels = vec![Some(RED); 2 * 6];
+        pixels.extend([Some(BLUE), None, None, None]);
+        let out = encode(&pixels, 2, 8);
+        let (attrs, registers, data) = split(&out);
+        assert_eq!(attrs, "\"1;1;2;8");
+        assert_eq!(registers.len(), 2);
+        let red = registers[&[100, 0, 0]];
+        let blue = registers[&[0, 0, 100]];
+        assert_eq!(data, format!("#{red}~~-#{blue}@"));
+    }
+
+    #[test]
+    fn encode_skips_missing_pixels() {
+        let pixels = [Some(RED), None, Some(RED), None, None];
+        let out = encode(&pixels, 5, 1);
+        let (attrs, registers, data) = split(&out);
+        assert_eq!(attrs, "\"1;1;5;1");
+        let red = registers[&[100, 0, 0]];
+        assert_eq!(data, format!("#{red}@?@"));
+
+        let out = encode(&[None; 12], 3, 4);
+        let (attrs, registers, data) = split(&out);
+        assert_eq!(attrs, "\"1;1;3;4");
+        assert!(registers.is_empty());
+        assert_eq!(data, "");
+    }
+
+    #[test]
+    fn encode_runs() {
+        let pixels = vec![Some(BLUE); 6];
+        let out = encode(&pixels, 6, 1);
+        let (_, registers, data) = split(&out);
+        let blue = registers[&[0, 0, 100]];
+        assert_eq!(data, format!("#{blue}!6@"));
+    }
+}
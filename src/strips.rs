@@ -0,0 +1,83 @@
+use crate::{Image, Result, ToneMap};
+use std::io::{BufRead, Seek};
+
+/// An iterator over horizontal strips of a decoded image, from top to bottom.
+/// See [`Image::load_strips`].
+#[derive(Debug)]
+pub struct ImageStrips {
+    source: image::DynamicImage,
+    format: Option<image::ImageFormat>,
+    strip_height: usize,
+    top: usize,
+}
+
+impl ImageStrips {
+    /// Gets the width of the whole image, which is also the width of every
+    /// strip
+    pub fn width(&self) -> usize {
+        self.source.width() as usize
+    }
+
+    /// Gets the height of the whole image
+    pub fn height(&self) -> usize {
+        self.source.height() as usize
+    }
+
+    /// Gets the height of the strips. The last strip may be shorter.
+    pub fn strip_height(&self) -> usize {
+        self.strip_height
+    }
+}
+
+impl Iterator for ImageStrips {
+    type Item = Image;
+
+    fn next(&mut self) -> Option<Image> {
+        if self.top >= self.height() {
+            return None;
+        }
+        let height = self.strip_height.min(self.height() - self.top);
+        let strip = self
+            .source
+            .crop_imm(0, self.top as u32, self.width() as u32, height as u32);
+        self.top += height;
+        Some(Image {
+            format: self.format,
+            ..Image::from_image(&strip, ToneMap::Clamp)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.height() - self.top).div_ceil(self.strip_height);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for ImageStrips {}
+
+impl Image {
+    /// Loads an image from a type implementing [`BufRead`] and [`Seek`] as a
+    /// sequence of horizontal strips of `strip_height` rows each (the last
+    /// strip may be shorter), from top to bottom. The image format is
+    /// automatically detected from the content.
+    ///
+    /// Each strip is converted to an [`Image`] only when the iterator
+    /// reaches it, so a caller can render or downsample a huge image (e.g. a
+    /// scan or panorama) incrementally and drop each strip once processed,
+    /// instead of holding the converted image in memory at once. Note that
+    /// the [`image`] crate does not offer incremental decoding, so the
+    /// source itself is currently decoded fully up front and kept in its
+    /// native pixel format until the iterator is dropped. A `strip_height`
+    /// of `0` is treated as `1`.
+    pub fn load_strips<R: BufRead + Seek>(r: R, strip_height: usize) -> Result<ImageStrips> {
+        let r = image::io::Reader::new(r).with_guessed_format()?;
+        let format = r.format();
+        let source = r.decode()?;
+        Ok(ImageStrips {
+            source,
+            format,
+            strip_height: strip_height.max(1),
+            top: 0,
+        })
+    }
+}
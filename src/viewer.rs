@@ -0,0 +1,214 @@
+use crate::{Fit, ImageView, Region};
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::Rect,
+    widgets::{StatefulWidget, Widget},
+};
+
+/// The largest zoom factor reachable with [`ViewState::zoom_in`]
+const MAX_ZOOM: f32 = 64.0;
+/// Factor applied by each zoom step
+const ZOOM_STEP: f32 = 1.25;
+/// Fraction of the visible region moved by each pan step
+const PAN_STEP: f32 = 0.1;
+
+/// An interactive image viewer widget, rendering an [`ImageView`] panned and
+/// zoomed according to a [`ViewState`].
+///
+/// The [`Region`] of the wrapped view is the full extent that can be viewed,
+/// and the state selects the visible portion of it. All other settings of the
+/// view are kept, except for the [`Fit`] mode, which is controlled by the
+/// state. Key events are translated into state changes with
+/// [`ViewState::handle_event`]:
+///
+/// | Key                         | Action                                  |
+/// |-----------------------------|-----------------------------------------|
+/// | `q`, `Esc`, `Ctrl+C`        | Quit (see [`ViewState::should_quit`])   |
+/// | Arrow keys, `h` `j` `k` `l` | Pan                                     |
+/// | `+`, `=`                    | Zoom in                                 |
+/// | `-`                         | Zoom out                                |
+/// | `f`                         | Toggle between [`Fit`] modes            |
+/// | `0`, `r`                    | Reset pan, zoom, and fit                |
+///
+/// Only available with the `interactive` feature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewer<'a> {
+    view: ImageView<'a>,
+}
+
+/// State of a [`Viewer`], holding the current pan, zoom, and [`Fit`] mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewState {
+    zoom: f32,
+    center_x: f32,
+    center_y: f32,
+    fit: Fit,
+    quit: bool,
+}
+
+impl<'a> Viewer<'a> {
+    /// Creates a viewer of `view`
+    pub fn new(view: ImageView<'a>) -> Self {
+        Self { view }
+    }
+
+    /// Gets the wrapped view
+    pub fn view(&self) -> &ImageView<'a> {
+        &self.view
+    }
+}
+
+impl<'a> From<ImageView<'a>> for Viewer<'a> {
+    fn from(view: ImageView<'a>) -> Self {
+        Self::new(view)
+    }
+}
+
+impl Default for ViewState {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            center_x: 0.5,
+            center_y: 0.5,
+            fit: Fit::Zoom,
+            quit: false,
+        }
+    }
+}
+
+impl ViewState {
+    /// Creates a state showing the entire view with [`Fit::Zoom`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the current zoom factor, where `1.0` shows the entire view
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Setter for the zoom factor. The factor is clamped to be at least `1.0`.
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = if zoom.is_finite() {
+            zoom.clamp(1.0, MAX_ZOOM)
+        } else {
+            1.0
+        };
+    }
+
+    /// Zooms in by one step
+    pub fn zoom_in(&mut self) {
+        self.set_zoom(self.zoom * ZOOM_STEP);
+    }
+
+    /// Zooms out by one step
+    pub fn zoom_out(&mut self) {
+        self.set_zoom(self.zoom / ZOOM_STEP);
+    }
+
+    /// Moves the visible portion of the view by `dx`, `dy` steps, where a step
+    /// is a tenth of the visible portion. Positive values move right and down.
+    pub fn pan(&mut self, dx: i32, dy: i32) {
+        let step = PAN_STEP / self.zoom;
+        self.center_x = (self.center_x + dx as f32 * step).clamp(0.0, 1.0);
+        self.center_y = (self.center_y + dy as f32 * step).clamp(0.0, 1.0);
+    }
+
+    /// Gets the current [`Fit`] mode
+    pub fn fit(&self) -> Fit {
+        self.fit
+    }
+
+    /// Setter for the [`Fit`] mode
+    pub fn set_fit(&mut self, fit: Fit) {
+        self.fit = fit;
+    }
+
+    /// Switches between [`Fit::Zoom`] and [`Fit::Stretch`]
+    pub fn toggle_fit(&mut self) {
+        self.fit = match self.fit {
+            Fit::Zoom => Fit::Stretch,
+            Fit::Stretch => Fit::Zoom,
+        };
+    }
+
+    /// Resets the pan, zoom, and [`Fit`] mode, keeping whether the viewer
+    /// should quit
+    pub fn reset(&mut self) {
+        *self = Self {
+            quit: self.quit,
+            ..Self::default()
+        };
+    }
+
+    /// Gets whether a quit key has been pressed
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Updates the state for a terminal event, returning whether the event was
+    /// handled. Only key press events are handled.
+    pub fn handle_event(&mut self, event: &Event) -> bool {
+        match event {
+            Event::Key(key) => self.handle_key(*key),
+            _ => false,
+        }
+    }
+
+    /// Updates the state for a key event, returning whether the key was
+    /// handled. See [`Viewer`] for the key bindings.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return false;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.quit = true
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.pan(-1, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.pan(1, 0),
+            KeyCode::Up | KeyCode::Char('k') => self.pan(0, -1),
+            KeyCode::Down | KeyCode::Char('j') => self.pan(0, 1),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom_in(),
+            KeyCode::Char('-') => self.zoom_out(),
+            KeyCode::Char('f') => self.toggle_fit(),
+            KeyCode::Char('0') | KeyCode::Char('r') => self.reset(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Computes the visible portion of `region` for the current pan and zoom.
+    ///
+    /// The result is at least 1 pixel wide and tall (unless `region` is
+    /// empty), and is kept within `region`.
+    pub fn visible_region(&self, region: Region) -> Region {
+        let visible = |len: usize, center: f32| {
+            let size = ((len as f32 / self.zoom).round() as usize).clamp(len.min(1), len);
+            let start = (center * len as f32 - size as f32 / 2.0).round().max(0.0) as usize;
+            (start.min(len - size), size)
+        };
+        let (x, width) = visible(region.width, self.center_x);
+        let (y, height) = visible(region.height, self.center_y);
+        Region {
+            x: region.x + x,
+            y: region.y + y,
+            width,
+            height,
+        }
+    }
+}
+
+impl<'a> StatefulWidget for Viewer<'a> {
+    type State = ViewState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let region = state.visible_region(*self.view.region());
+        self.view
+            .with_region(region)
+            .with_fit(state.fit)
+            .render(area, buf);
+    }
+}